@@ -14,15 +14,61 @@
 // - HTML reports with detailed performance metrics
 // - Protection against compiler optimizations via black_box()
 //
-// The benchmarks focus on two critical game operations:
-// 1. Tile movement logic (move_tiles method)
+// The benchmarks focus on three critical game operations:
+// 1. Tile movement logic (move_tiles method), in every direction and at a
+//    range of board densities
 // 2. Game over detection (check_game_over method)
+// 3. AI move selection (best_move and expectimax_move methods)
 //
 // These operations are benchmarked under different grid states to understand
 // performance characteristics across various gameplay scenarios.
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use rust_2048_game::{Direction, GameState, GRID_SIZE};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use rust_2048_game::{AiStrategy, Direction, GameState, GRID_SIZE};
+
+// === BENCHMARK BOARDS ===
+// Boards are written in packed form (see GameState::from_packed): each hex digit
+// is one cell's base-2 exponent in reading order, so 0x1000_0000_0000_0002 has a
+// 2 in the top-left corner and a 4 in the bottom-right. Fixed boards keep the
+// measurements deterministic from run to run.
+
+/// No tiles at all
+const EMPTY_BOARD: u64 = 0x0000_0000_0000_0000;
+
+/// 25% full (4 tiles) with no merges available
+const QUARTER_FULL_BOARD: u64 = 0x1000_0200_0030_0004;
+
+/// 50% full (8 tiles) with a couple of merges available
+const HALF_FULL_BOARD: u64 = 0x1020_0304_1020_0304;
+
+/// 75% full (12 tiles) with a mix of slides and merges
+const THREE_QUARTERS_FULL_BOARD: u64 = 0x1203_2130_0321_1232;
+
+/// Every cell filled with a 2, so every move merges as much as possible
+const FULL_BOARD: u64 = 0x1111_1111_1111_1111;
+
+/// A typical mid-game position: large tiles gathered in the top-left corner,
+/// small tiles scattered toward the bottom-right
+const MID_GAME_BOARD: u64 = 0x9876_4523_2310_1001;
+
+/// An open mid-game position: big tiles built up in the top-left corner and
+/// nine empty cells, so every chance node branches widely
+const OPEN_MID_GAME_BOARD: u64 = 0x9870_6500_3000_1000;
+
+/// A late-game position with only two empty cells and few merges
+const CROWDED_BOARD: u64 = 0xA987_5634_2302_1021;
+
+/// Full rows of equal pairs: moving right merges every pair and slides nothing
+/// more than one cell
+const MERGE_HEAVY_BOARD: u64 = 0x1122_3344_1122_3344;
+
+/// Distinct tiles packed against the left edge: moving right slides every tile
+/// across the whole row without merging anything
+const SLIDE_HEAVY_BOARD: u64 = 0x1000_2000_3000_4000;
+
+/// Seed of the random games that GameState::realistic_board plays, fixed so
+/// the generated boards are the same on every run
+const REALISTIC_BOARD_SEED: u64 = 2048;
 
 /// Benchmarks the tile movement algorithm under different grid conditions
 ///
@@ -39,45 +85,72 @@ use rust_2048_game::{Direction, GameState, GRID_SIZE};
 /// - Algorithm complexity varies significantly with grid density
 ///
 /// BENCHMARKING STRATEGY:
-/// We test two extreme scenarios to understand performance bounds:
-/// 1. Empty grid: Minimal computational work (early exit conditions)
-/// 2. Full grid: Maximum computational work (all cells need processing)
+/// 1. Every direction on boards from empty to full (0/25/50/75/100%), plus a
+///    realistic mid-game position, since each direction walks the grid in a
+///    different order
+/// 2. A merge-heavy board against a slide-heavy board, to separate the cost of
+///    merging from the cost of moving tiles
+/// 3. Boards reached by seeded random play (GameState::realistic_board) at
+///    increasing densities, whose tile layouts resemble real games more than
+///    the hand-written boards do
 ///
-/// POTENTIAL IMPROVEMENTS:
-/// - Could test all four directions (Up, Down, Left, Right)
-/// - Missing intermediate grid densities (25%, 50%, 75% full)
-/// - No testing of merge-heavy scenarios vs slide-heavy scenarios
-/// - Could benchmark with realistic game states (saved from actual gameplay)
+/// Each iteration starts from a freshly built board (outside the timed
+/// section), so every measured move does the same amount of work instead of
+/// acting on the result of the previous iteration.
 fn benchmark_move_tiles(c: &mut Criterion) {
     let mut group = c.benchmark_group("move_tiles");
 
-    // Set up test data: empty grid (worst case for early exit optimization)
-    let mut empty_state = GameState::new();
-    // Clear the initial tiles that GameState::new() adds by default
-    empty_state.grid = [[0; GRID_SIZE as usize]; GRID_SIZE as usize];
+    let boards = [
+        ("empty", EMPTY_BOARD),
+        ("25pct", QUARTER_FULL_BOARD),
+        ("50pct", HALF_FULL_BOARD),
+        ("75pct", THREE_QUARTERS_FULL_BOARD),
+        ("full", FULL_BOARD),
+        ("mid_game", MID_GAME_BOARD),
+    ];
 
-    // Set up test data: completely full grid (worst case for computational complexity)
-    let mut full_state = GameState::new();
-    // Fill every cell with the value 2 (creates maximum merging opportunities)
-    for i in 0..GRID_SIZE as usize {
-        for j in 0..GRID_SIZE as usize {
-            full_state.grid[i][j] = 2;
+    // Benchmark 1: Every direction at every density
+    for (name, packed) in boards {
+        for direction in Direction::ALL {
+            let id = format!("move_{:?}_{}", direction, name).to_lowercase();
+            group.bench_function(id, |b| {
+                b.iter_batched(
+                    || GameState::from_packed(packed),
+                    |mut state| state.move_tiles(black_box(direction)),
+                    BatchSize::SmallInput,
+                )
+            });
         }
     }
 
-    // Benchmark 1: Movement on empty grid
-    // Expected: Very fast execution due to early exit conditions
-    // This measures the overhead of the movement algorithm when no work is needed
-    group.bench_function("move_right_empty_state", |b| {
-        b.iter(|| empty_state.move_tiles(black_box(Direction::Right)))
-    });
+    // Benchmark 2: Merge-heavy vs slide-heavy work in the same direction
+    for (name, packed) in [
+        ("merge_heavy", MERGE_HEAVY_BOARD),
+        ("slide_heavy", SLIDE_HEAVY_BOARD),
+    ] {
+        group.bench_function(format!("move_right_{}", name), |b| {
+            b.iter_batched(
+                || GameState::from_packed(packed),
+                |mut state| state.move_tiles(black_box(Direction::Right)),
+                BatchSize::SmallInput,
+            )
+        });
+    }
 
-    // Benchmark 2: Movement on completely full grid
-    // Expected: Slower execution as algorithm must check every cell
-    // This measures maximum computational load of the movement algorithm
-    group.bench_function("move_right_full_state", |b| {
-        b.iter(|| full_state.move_tiles(black_box(Direction::Right)))
-    });
+    // Benchmark 3: Lifelike boards from seeded random play
+    for density in [0.25, 0.5, 0.75] {
+        let grid = GameState::<4>::realistic_board(REALISTIC_BOARD_SEED, density).grid;
+        for direction in Direction::ALL {
+            let id = format!("move_{:?}_realistic_{}pct", direction, density * 100.0);
+            group.bench_function(id.to_lowercase(), |b| {
+                b.iter_batched(
+                    || GameState::from_grid(grid),
+                    |mut state| state.move_tiles(black_box(direction)),
+                    BatchSize::SmallInput,
+                )
+            });
+        }
+    }
 
     group.finish();
 }
@@ -141,5 +214,58 @@ fn benchmark_game_over(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_move_tiles, benchmark_game_over);
+/// Benchmarks the AI's move selection
+///
+/// WHAT IS BEING BENCHMARKED:
+/// - best_move() with each AiStrategy (a single ply of lookahead for Greedy and
+///   Corner; Expectimax searches DEFAULT_EXPECTIMAX_DEPTH moves ahead)
+/// - expectimax_move() at increasing search depths
+///
+/// WHY BENCHMARK THIS:
+/// - The expectimax search is by far the most expensive operation in the crate
+/// - Its cost grows exponentially with depth and with the number of empty
+///   cells, so small changes to the search or heuristic can have large effects
+///
+/// BENCHMARKING STRATEGY:
+/// An open mid-game board (nine empty cells, wide chance nodes) and a crowded
+/// late-game board (few empty cells, narrow chance nodes but deeper play), each
+/// built from a fixed packed value so every run searches the same tree.
+fn benchmark_ai(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ai");
+    // Deep searches take milliseconds each; fewer samples keep the run short
+    group.sample_size(10);
+
+    let boards = [
+        ("open", GameState::from_packed(OPEN_MID_GAME_BOARD)),
+        ("crowded", GameState::from_packed(CROWDED_BOARD)),
+    ];
+
+    for (name, state) in &boards {
+        // Benchmark 1: best_move() for every strategy
+        for strategy in [
+            AiStrategy::Greedy,
+            AiStrategy::Corner,
+            AiStrategy::Expectimax,
+        ] {
+            let id = format!("best_move_{:?}_{}", strategy, name).to_lowercase();
+            group.bench_function(id, |b| b.iter(|| state.best_move(black_box(strategy))));
+        }
+
+        // Benchmark 2: expectimax_move() across search depths
+        for depth in 0..=3 {
+            group.bench_function(format!("expectimax_depth{}_{}", depth, name), |b| {
+                b.iter(|| state.expectimax_move(black_box(depth)))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_move_tiles,
+    benchmark_game_over,
+    benchmark_ai
+);
 criterion_main!(benches);
@@ -0,0 +1,1113 @@
+// 2048 Game AI - Board Evaluation, Move Selection and Adversarial Play
+//
+// This module extends GameState with computer-controlled behavior:
+// - A board evaluation heuristic shared by all AI features, built from empty
+//   cells, available merges, monotonicity and smoothness
+// - Move selection strategies (greedy, corner-hugging, expectation-based)
+// - An "evil" tile spawner that places tiles where they hurt the player most
+// - Risk detection that flags moves an unlucky spawn could turn into a loss
+// - A bounded solver that finds a line of moves reaching a target tile
+//
+// Searches work on bare grids via board::slide() rather than cloning GameState,
+// so simulating a move costs no more than copying 16 integers.
+
+use crate::board;
+use crate::{Direction, GameState, GameStats, Grid};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Weight of monotonicity() in the board evaluation
+const MONOTONICITY_WEIGHT: f32 = 1.0;
+
+/// Weight of smoothness() in the board evaluation
+///
+/// Kept small because the raw score grows with every adjacent pair on the board.
+const SMOOTHNESS_WEIGHT: f32 = 0.1;
+
+/// Search depth used by best_move() with the Expectimax strategy
+pub const DEFAULT_EXPECTIMAX_DEPTH: u32 = 2;
+
+/// Value subtracted from a board with no legal moves, so the search steers
+/// away from lines that end the game
+const GAME_OVER_PENALTY: f32 = 1000.0;
+
+/// Number of moves auto_arrange_demo() plays at most
+pub const DEMO_MOVE_LIMIT: usize = 32;
+
+// === AI STRATEGY ENUM ===
+
+/// Strategy used by best_move() to choose a direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AiStrategy {
+    /// Pick the move with the best immediate result (points gained plus evaluation)
+    #[default]
+    Greedy,
+    /// Like Greedy, but strongly prefer keeping the largest tile in the top-left
+    /// corner with values decreasing away from it
+    Corner,
+    /// Search ahead with expectimax_move() at DEFAULT_EXPECTIMAX_DEPTH
+    Expectimax,
+}
+
+// === BOARD EVALUATION ===
+
+/// Scores a grid for the player: empty cells, available merges, monotonicity
+/// and smoothness
+///
+/// See GameState::evaluate() for details.
+pub(crate) fn evaluate_grid<const N: usize>(grid: &Grid<N>) -> f32 {
+    let empty = grid.iter().flatten().filter(|&&cell| cell == 0).count();
+    (empty + merge_opportunities(grid)) as f32
+        + MONOTONICITY_WEIGHT * monotonicity_grid(grid)
+        + SMOOTHNESS_WEIGHT * smoothness_grid(grid)
+}
+
+/// Counts pairs of orthogonally adjacent non-empty tiles with equal values
+///
+/// Like has_moves_available(), only the right and down neighbors are checked
+/// so each pair is counted exactly once.
+fn merge_opportunities<const N: usize>(grid: &Grid<N>) -> usize {
+    let mut pairs = 0;
+    for i in 0..N {
+        for j in 0..N {
+            let current = grid[i][j];
+            if current == 0 {
+                continue;
+            }
+            if j < N - 1 && current == grid[i][j + 1] {
+                pairs += 1;
+            }
+            if i < N - 1 && current == grid[i + 1][j] {
+                pairs += 1;
+            }
+        }
+    }
+    pairs
+}
+
+/// Converts a tile value to its base-2 logarithm, treating empty cells as 0
+///
+/// Heuristics compare tiles on a log scale so a 1024 next to a 2048 counts as
+/// one step apart, just like a 2 next to a 4.
+fn log2_value(cell: u32) -> f32 {
+    if cell == 0 {
+        0.0
+    } else {
+        (cell as f32).log2()
+    }
+}
+
+/// Penalty for one row or column not being monotonic
+///
+/// Sums the log2 steps that go against increasing order and, separately,
+/// against decreasing order, then keeps the smaller of the two: a line that is
+/// sorted in either direction costs nothing.
+fn line_disorder<const N: usize>(line: [u32; N]) -> f32 {
+    let mut against_increasing = 0.0;
+    let mut against_decreasing = 0.0;
+    for pair in line.windows(2) {
+        let (current, next) = (log2_value(pair[0]), log2_value(pair[1]));
+        if current > next {
+            against_increasing += current - next;
+        } else {
+            against_decreasing += next - current;
+        }
+    }
+    f32::min(against_increasing, against_decreasing)
+}
+
+/// Scores how consistently tiles increase or decrease along rows and columns
+///
+/// See GameState::monotonicity() for details.
+pub(crate) fn monotonicity_grid<const N: usize>(grid: &Grid<N>) -> f32 {
+    let rows: f32 = grid.iter().map(|&row| line_disorder(row)).sum();
+    let columns: f32 = (0..N).map(|j| line_disorder(grid.map(|row| row[j]))).sum();
+    -(rows + columns)
+}
+
+/// Scores how close in value neighboring tiles are
+///
+/// See GameState::smoothness() for details.
+pub(crate) fn smoothness_grid<const N: usize>(grid: &Grid<N>) -> f32 {
+    let mut roughness = 0.0;
+    for i in 0..N {
+        for j in 0..N {
+            let current = grid[i][j];
+            if current == 0 {
+                continue;
+            }
+            if j < N - 1 && grid[i][j + 1] != 0 {
+                roughness += (log2_value(current) - log2_value(grid[i][j + 1])).abs();
+            }
+            if i < N - 1 && grid[i + 1][j] != 0 {
+                roughness += (log2_value(current) - log2_value(grid[i + 1][j])).abs();
+            }
+        }
+    }
+    -roughness
+}
+
+/// Measures how scattered the tiles are among the empty cells
+///
+/// See GameState::fragmentation() for details.
+pub(crate) fn fragmentation_grid<const N: usize>(grid: &Grid<N>) -> f32 {
+    let mut mixed = 0;
+    for i in 0..N {
+        for j in 0..N {
+            let occupied = grid[i][j] != 0;
+            if j < N - 1 && occupied != (grid[i][j + 1] != 0) {
+                mixed += 1;
+            }
+            if i < N - 1 && occupied != (grid[i + 1][j] != 0) {
+                mixed += 1;
+            }
+        }
+    }
+    let pairs = 2 * N * (N - 1);
+    if pairs == 0 {
+        return 0.0;
+    }
+    mixed as f32 / pairs as f32
+}
+
+/// Scores a grid for the corner strategy
+///
+/// Adds to evaluate_grid() a positional term that weights each tile's log2
+/// value by its closeness to the top-left corner (weights fall by one per step
+/// away from the corner), and subtracts a penalty proportional to how far the
+/// largest tile has drifted from that corner.
+fn corner_evaluate<const N: usize>(grid: &Grid<N>) -> f32 {
+    let last = N - 1;
+    let mut positional = 0.0;
+    let mut max_tile = 0;
+    let mut max_position = (0, 0);
+
+    for (i, row) in grid.iter().enumerate() {
+        for (j, &cell) in row.iter().enumerate() {
+            if cell == 0 {
+                continue;
+            }
+            let weight = (2 * last - (i + j) + 1) as f32;
+            positional += (cell as f32).log2() * weight;
+            if cell > max_tile {
+                max_tile = cell;
+                max_position = (i, j);
+            }
+        }
+    }
+
+    let distance = (max_position.0 + max_position.1) as f32;
+    let penalty = if max_tile == 0 {
+        0.0
+    } else {
+        (max_tile as f32).log2() * distance * N as f32
+    };
+
+    evaluate_grid(grid) + positional - penalty
+}
+
+impl<const N: usize> GameState<N> {
+    /// Scores how favorable the current board is for the player
+    ///
+    /// The heuristic rewards four things that keep a game alive:
+    /// 1. Empty cells (room to maneuver and spawn)
+    /// 2. Orthogonally adjacent equal tiles (merges available next move)
+    /// 3. Ordered rows and columns, via monotonicity()
+    /// 4. Neighbors of similar value, via smoothness()
+    ///
+    /// The last two are penalties (never positive), weighted by
+    /// MONOTONICITY_WEIGHT and SMOOTHNESS_WEIGHT.
+    ///
+    /// Higher scores are better for the player. Adversarial features use the
+    /// same heuristic inverted, preferring the lowest-scoring board.
+    ///
+    /// # Returns
+    ///
+    /// * `f32` - The evaluation score (higher is better for the player)
+    pub fn evaluate(&self) -> f32 {
+        evaluate_grid(&self.grid)
+    }
+
+    /// Counts the pairs of orthogonally adjacent tiles that could merge
+    ///
+    /// Where has_moves_available() stops at the first matching neighbor, this
+    /// counts every pair, so it measures how much merge potential the board
+    /// holds. Each pair is counted once; equal tiles separated by an empty cell
+    /// are not counted.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - Number of adjacent pairs of equal, non-empty tiles
+    pub fn adjacent_pairs(&self) -> usize {
+        merge_opportunities(&self.grid)
+    }
+
+    /// Measures how orderly the board is, a classic 2048 heuristic
+    ///
+    /// A row or column is monotonic when its tiles only increase or only
+    /// decrease along it. Each line is charged the log2 steps that break its
+    /// better direction, and the score is the negated total over all rows and
+    /// columns. Rows may run in different directions, so a snake-ordered board
+    /// scores perfectly.
+    ///
+    /// # Returns
+    ///
+    /// * `f32` - 0.0 for a perfectly monotonic board, lower (more negative) the
+    ///   more disordered the tiles are
+    pub fn monotonicity(&self) -> f32 {
+        monotonicity_grid(&self.grid)
+    }
+
+    /// Measures how close in value adjacent tiles are, a classic 2048 heuristic
+    ///
+    /// Every pair of orthogonally adjacent non-empty tiles is charged the
+    /// difference of their log2 values, and the score is the negated total.
+    /// Neighbors with similar values are only a few merges apart, so a smoother
+    /// board is easier to consolidate. Empty cells are skipped.
+    ///
+    /// # Returns
+    ///
+    /// * `f32` - 0.0 when every pair of neighbors is equal, lower (more
+    ///   negative) the further apart neighboring values are
+    pub fn smoothness(&self) -> f32 {
+        smoothness_grid(&self.grid)
+    }
+
+    /// Measures how scattered the tiles are, the wasted space of a board
+    ///
+    /// Counts the pairs of orthogonally adjacent cells where one cell holds a
+    /// tile and the other is empty, divided by the number of adjacent pairs.
+    /// Tiles packed together with their free space in one block share few such
+    /// borders, while tiles strewn across the board leave empty cells that are
+    /// hard to fill usefully. Complements monotonicity() and smoothness(),
+    /// which ignore empty cells.
+    ///
+    /// # Returns
+    ///
+    /// * `f32` - From 0.0 for a full or empty board to 1.0
+    ///   for a checkerboard of tiles and gaps; lower is tidier
+    pub fn fragmentation(&self) -> f32 {
+        fragmentation_grid(&self.grid)
+    }
+
+    /// Simulates a move on a copy of the board, without spawning a tile
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(Grid<N>, board::Slide)>` - The resulting board and what the
+    ///   slide did, or None if the move would not change the board or is
+    ///   refused because of blocked cells
+    pub(crate) fn simulate(&self, direction: Direction) -> Option<(Grid<N>, board::Slide)> {
+        if self.blocks_direction(direction) {
+            return None;
+        }
+        let mut grid = self.grid;
+        let slide = board::slide(&mut grid, direction, self.merge_rules());
+        slide.moved.then_some((grid, slide))
+    }
+
+    /// Returns the points a move would earn, without changing the game
+    ///
+    /// The move is simulated on a copy of the board and no tile is spawned.
+    /// The result follows the current scoring rules, including the combo bonus
+    /// when combo scoring is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The move to evaluate
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The points the move would add to score(); 0 if nothing merges
+    ///   or the move is not legal
+    pub fn score_gain(&self, direction: Direction) -> u32 {
+        self.simulate(direction)
+            .map_or(0, |(_, slide)| self.slide_points(slide))
+    }
+}
+
+// === MOVE SELECTION ===
+
+impl<const N: usize> GameState<N> {
+    /// Suggests the best direction to move according to the given strategy
+    ///
+    /// Each legal move is simulated and scored as the points it earns plus a
+    /// strategy-specific evaluation of the resulting board:
+    /// * `Greedy` - evaluate() of the board right after the move
+    /// * `Corner` - evaluate() plus a bonus for keeping the largest tile in the
+    ///   top-left corner with values decreasing away from it
+    /// * `Expectimax` - the value found by expectimax_move() searching
+    ///   DEFAULT_EXPECTIMAX_DEPTH moves ahead
+    ///
+    /// Ties are broken in the order of Direction::ALL.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - How to score candidate moves
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Direction>` - The best move, or None if no move changes the board
+    pub fn best_move(&self, strategy: AiStrategy) -> Option<Direction> {
+        let score: fn(&Grid<N>) -> f32 = match strategy {
+            AiStrategy::Greedy => evaluate_grid,
+            AiStrategy::Corner => corner_evaluate,
+            AiStrategy::Expectimax => return self.expectimax_move(DEFAULT_EXPECTIMAX_DEPTH),
+        };
+
+        let mut best: Option<(Direction, f32)> = None;
+        for direction in Direction::ALL {
+            let Some((grid, slide)) = self.simulate(direction) else {
+                continue;
+            };
+            let value = slide.gained as f32 + score(&grid);
+            if best.is_none_or(|(_, best_value)| value > best_value) {
+                best = Some((direction, value));
+            }
+        }
+
+        best.map(|(direction, _)| direction)
+    }
+
+    /// Finds the best move with a depth-limited expectimax search
+    ///
+    /// The search alternates two kinds of nodes:
+    /// 1. Move nodes take the maximum over the allowed directions of the points
+    ///    a move earns plus the value of the chance node that follows it
+    /// 2. Chance nodes average over every open cell receiving each value of
+    ///    the spawn range, weighted as add_random_tile() draws them (a 2 90% and
+    ///    a 4 10% of the time by default)
+    ///
+    /// `depth` counts how many further moves are searched after the first one.
+    /// Chance nodes at depth 0 are scored with evaluate(), so depth 0 makes the
+    /// same choice as AiStrategy::Greedy. Boards with no legal moves are
+    /// heavily penalized.
+    ///
+    /// Chance node values are cached in a transposition table keyed by the
+    /// canonical form of the board, so positions that are rotations or
+    /// reflections of each other are only searched once. During row practice
+    /// the blocked rows break that symmetry, so boards are keyed as they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - Number of moves to look ahead beyond the first
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Direction>` - The best move, or None if no move changes the board
+    pub fn expectimax_move(&self, depth: u32) -> Option<Direction> {
+        let spawns = self.spawn_distribution();
+        let mut search = Expectimax {
+            game: self,
+            rules: self.merge_rules(),
+            spawns: &spawns,
+            table: HashMap::new(),
+        };
+
+        let mut best: Option<(Direction, f32)> = None;
+        for direction in Direction::ALL {
+            let Some((grid, slide)) = self.simulate(direction) else {
+                continue;
+            };
+            let value = slide.gained as f32 + search.chance_value(&grid, depth);
+            if best.is_none_or(|(_, best_value)| value > best_value) {
+                best = Some((direction, value));
+            }
+        }
+
+        best.map(|(direction, _)| direction)
+    }
+}
+
+// === EXPECTIMAX SEARCH ===
+
+/// State shared across one expectimax search
+struct Expectimax<'a, const N: usize> {
+    /// The game being searched, for its blocked directions and cells
+    game: &'a GameState<N>,
+    /// Merge rules of the game being searched
+    rules: board::MergeRules,
+    /// Values a spawn can take with their probabilities, from spawn_distribution()
+    spawns: &'a [(u32, f32)],
+    /// Cached chance node values keyed by (canonical board, remaining depth)
+    table: HashMap<(Grid<N>, u32), f32>,
+}
+
+impl<const N: usize> Expectimax<'_, N> {
+    /// Best value the player can reach from a board, choosing the next move
+    fn max_value(&mut self, grid: &Grid<N>, depth: u32) -> f32 {
+        let mut best: Option<f32> = None;
+        for direction in Direction::ALL {
+            if self.game.blocks_direction(direction) {
+                continue;
+            }
+            let mut next = *grid;
+            let slide = board::slide(&mut next, direction, self.rules);
+            if !slide.moved {
+                continue;
+            }
+            let value = slide.gained as f32 + self.chance_value(&next, depth);
+            best = Some(best.map_or(value, |current| current.max(value)));
+        }
+        best.unwrap_or_else(|| evaluate_grid(grid) - GAME_OVER_PENALTY)
+    }
+
+    /// Expected value of a board just before a random tile spawns
+    fn chance_value(&mut self, grid: &Grid<N>, depth: u32) -> f32 {
+        if depth == 0 {
+            return evaluate_grid(grid);
+        }
+
+        let key = match self.game.practice_row() {
+            Some(_) => (*grid, depth),
+            None => (board::canonical_grid(grid), depth),
+        };
+        if let Some(&value) = self.table.get(&key) {
+            return value;
+        }
+
+        let mut total = 0.0;
+        let mut cells = 0;
+        let mut spawned = *grid;
+        for i in 0..N {
+            for j in 0..N {
+                if grid[i][j] != 0 || self.game.is_cell_blocked(i, j) {
+                    continue;
+                }
+                cells += 1;
+                for &(value, chance) in self.spawns {
+                    spawned[i][j] = value;
+                    total += chance * self.max_value(&spawned, depth - 1);
+                }
+                spawned[i][j] = 0;
+            }
+        }
+
+        // A move always frees at least one cell, but guard against a full board
+        let value = if cells == 0 {
+            self.max_value(grid, depth - 1)
+        } else {
+            total / cells as f32
+        };
+
+        self.table.insert(key, value);
+        value
+    }
+}
+
+// === DEMO PLAY ===
+
+impl<const N: usize> GameState<N> {
+    /// Plays a short scripted showcase that piles the largest tile into a corner
+    ///
+    /// Repeatedly plays best_move() with AiStrategy::Corner through
+    /// apply_move(), so tiles spawn as in a normal game, for up to
+    /// DEMO_MOVE_LIMIT moves. Stops early if the game ends or no move changes
+    /// the board. Meant for demos and attract screens rather than strong play.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<Direction>` - The moves played, in order
+    pub fn auto_arrange_demo(&mut self) -> Vec<Direction> {
+        let mut played = Vec::new();
+        while played.len() < DEMO_MOVE_LIMIT && !self.game_over {
+            let Some(direction) = self.best_move(AiStrategy::Corner) else {
+                break;
+            };
+            self.apply_move(direction);
+            played.push(direction);
+        }
+        played
+    }
+
+    /// Plays up to `moves` uniformly random legal moves through apply_move()
+    ///
+    /// Only directions from available_moves() are picked, so every move changes
+    /// the board and spawns a tile from the game's own generator. Stops early at
+    /// game over. Useful for property tests and for generating realistic boards
+    /// for benchmarks.
+    ///
+    /// # Arguments
+    ///
+    /// * `moves` - Maximum number of moves to play
+    /// * `rng` - Random number generator choosing the moves
+    pub fn play_random(&mut self, moves: usize, rng: &mut impl Rng) {
+        for _ in 0..moves {
+            let legal = self.available_moves();
+            if self.game_over || legal.is_empty() {
+                break;
+            }
+            self.apply_move(legal[rng.gen_range(0..legal.len())]);
+        }
+    }
+
+    /// Plays a whole game of random legal moves and reports how it went
+    ///
+    /// The game starts from reset_with_seed(seed) and play_random() runs until
+    /// game over, with the moves drawn from a second generator seeded with the
+    /// same value. Running many seeds characterizes how hard the game is for a
+    /// player with no strategy at all.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for both the spawns and the choice of moves
+    ///
+    /// # Returns
+    ///
+    /// * `GameStats` - The final statistics, identical for the same seed;
+    ///   `duration_secs` is always 0 since wall-clock time is not reproducible
+    pub fn simulate_random_game(seed: u64) -> GameStats {
+        let mut game = Self::blank();
+        game.reset_with_seed(seed);
+        game.play_random(usize::MAX, &mut StdRng::seed_from_u64(seed));
+        GameStats {
+            duration_secs: 0,
+            ..game.stats()
+        }
+    }
+
+    /// Builds a lifelike board by playing a seeded game until it is full enough
+    ///
+    /// Random legal moves are played one at a time, as in play_random(), until
+    /// density() reaches `target_density` or the game ends. Because merges and
+    /// spawns change the tile count in steps, the result lands on the first
+    /// board at or above the target rather than exactly on it. The same seed
+    /// and target always give the same board, which makes these boards
+    /// suitable for benchmarks and tests where hand-written grids of 2s would
+    /// be unrealistically uniform.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for both the spawns and the choice of moves
+    /// * `target_density` - Fraction of occupied cells to reach, clamped to
+    ///   0.0..=1.0
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The game at the first board reaching the target, or at game
+    ///   over if it never does
+    pub fn realistic_board(seed: u64, target_density: f32) -> Self {
+        let target_density = target_density.clamp(0.0, 1.0);
+        let mut game = Self::blank();
+        game.reset_with_seed(seed);
+        let mut rng = StdRng::seed_from_u64(seed);
+        while game.density() < target_density && !game.game_over {
+            game.play_random(1, &mut rng);
+        }
+        game
+    }
+
+    /// Estimates how many more moves the game can last under random play
+    ///
+    /// Plays `rollouts` independent games from the current board to game over
+    /// with play_random(), each on a lightweight copy of this game, so every
+    /// rule and spawn setting applies. A generator seeded with `seed` hands each
+    /// rollout two seeds of its own, one for its spawns and one for its moves,
+    /// so the estimate is reproducible and this game's own generator is left
+    /// untouched. Random
+    /// play is far weaker than a person, so the value is best read as a
+    /// relative measure of how precarious the position is.
+    ///
+    /// # Arguments
+    ///
+    /// * `rollouts` - Number of random games to average over
+    /// * `seed` - Seed from which every rollout's generators are derived
+    ///
+    /// # Returns
+    ///
+    /// * `f32` - Mean number of moves survived, or 0.0 when `rollouts` is 0
+    pub fn estimate_remaining_moves(&self, rollouts: usize, seed: u64) -> f32 {
+        if rollouts == 0 {
+            return 0.0;
+        }
+
+        let mut seeds = StdRng::seed_from_u64(seed);
+        let mut survived = 0;
+        for _ in 0..rollouts {
+            let mut game = self.simulation_copy();
+            game.rng = StdRng::seed_from_u64(seeds.gen());
+            game.play_random(usize::MAX, &mut StdRng::seed_from_u64(seeds.gen()));
+            survived += u64::from(game.moves - self.moves);
+        }
+        survived as f32 / rollouts as f32
+    }
+}
+
+// === SOLVER ===
+
+impl<const N: usize> GameState<N> {
+    /// Finds a short sequence of moves that creates a tile of at least `target`,
+    /// for a "show me how" hint button
+    ///
+    /// Runs a breadth-first search over the boards reachable by sliding, so the
+    /// first line found is also the shortest. Tiles that would spawn between
+    /// moves are not modeled, since their position is random: the line only
+    /// uses the tiles already on the board, and a spawn can block it. Ask again
+    /// after each move to follow the hint with up-to-date information. Boards
+    /// reached along several lines are only searched once, and directions
+    /// blocked during row practice are never suggested.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Tile value to reach
+    /// * `max_depth` - Maximum number of moves in the returned sequence
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Vec<Direction>>` - The moves to play in order (empty if the
+    ///   target is already on the board), or None if no line within
+    ///   `max_depth` moves reaches it
+    pub fn solve_toward(&self, target: u32, max_depth: u32) -> Option<Vec<Direction>> {
+        let reached = |grid: &Grid<N>| grid.iter().flatten().any(|&cell| cell >= target);
+        if reached(&self.grid) {
+            return Some(Vec::new());
+        }
+
+        let rules = self.merge_rules();
+        let mut visited = HashSet::from([self.grid]);
+        let mut queue = VecDeque::from([(self.grid, Vec::new())]);
+
+        while let Some((grid, line)) = queue.pop_front() {
+            if line.len() as u32 >= max_depth {
+                continue;
+            }
+            for direction in Direction::ALL {
+                let mut next = grid;
+                if self.blocks_direction(direction)
+                    || !board::slide(&mut next, direction, rules).moved
+                    || !visited.insert(next)
+                {
+                    continue;
+                }
+
+                let mut next_line = line.clone();
+                next_line.push(direction);
+                if reached(&next) {
+                    return Some(next_line);
+                }
+                queue.push_back((next, next_line));
+            }
+        }
+        None
+    }
+}
+
+// === RISK DETECTION ===
+
+impl<const N: usize> GameState<N> {
+    /// Warns whether a move could end the game, for a "careful mode"
+    ///
+    /// The move is simulated on a copy of the board, then every possible spawn
+    /// (each value of the spawn range in each open cell the move leaves empty)
+    /// is tried. If any of them leaves no legal move, judged as in
+    /// has_moves_available(), an unlucky spawn would end the game, so the move
+    /// is flagged as risky. No random numbers are drawn
+    /// and the game is unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The move to check
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if some spawn after this move ends the game; false if
+    ///   every spawn leaves a move available, or if the move changes nothing
+    pub fn move_leads_to_game_over(&self, direction: Direction) -> bool {
+        let Some((mut grid, _)) = self.simulate(direction) else {
+            return false;
+        };
+
+        for i in 0..N {
+            for j in 0..N {
+                if grid[i][j] != 0 || self.is_cell_blocked(i, j) {
+                    continue;
+                }
+                for value in self.spawn_values() {
+                    grid[i][j] = value;
+                    let stuck = !self.grid_has_moves(&grid);
+                    grid[i][j] = 0;
+                    if stuck {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+// === ADVERSARIAL SPAWNING ===
+
+impl<const N: usize> GameState<N> {
+    /// Places a new tile where it hurts the player the most ("evil 2048")
+    ///
+    /// Instead of choosing a random cell, every empty cell that is not blocked
+    /// (see is_cell_blocked()) is tried with every
+    /// value of the spawn range (2 and 4 by default, see
+    /// set_spawn_exponent_range()), and the placement leaving the lowest
+    /// evaluate() score is kept.
+    /// Since every placement fills exactly one cell, this effectively chooses the
+    /// spot and value that remove the most merge opportunities while leaving the
+    /// board as disordered and uneven as possible.
+    ///
+    /// # Behavior
+    ///
+    /// * Does nothing if no open empty cells are available
+    /// * Ties are broken by scanning order (top-left first, smaller values
+    ///   first), so the result is fully deterministic
+    pub fn worst_spawn(&mut self) {
+        let mut worst: Option<(usize, usize, u32, f32)> = None;
+
+        for i in 0..N {
+            for j in 0..N {
+                if self.grid[i][j] != 0 || self.is_cell_blocked(i, j) {
+                    continue;
+                }
+                for value in self.spawn_values() {
+                    // Try the placement in place, score it, then undo it
+                    self.grid[i][j] = value;
+                    let score = self.evaluate();
+                    self.grid[i][j] = 0;
+
+                    if worst.is_none_or(|(_, _, _, best)| score < best) {
+                        worst = Some((i, j, value, score));
+                    }
+                }
+            }
+        }
+
+        if let Some((i, j, value, _)) = worst {
+            self.grid[i][j] = value;
+            self.spawned_cells.push((i, j));
+        }
+    }
+
+    /// Enables or disables evil mode, where apply_move() spawns tiles with
+    /// worst_spawn() instead of add_random_tile()
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - True to play against adversarial spawns
+    pub fn set_evil_mode(&mut self, enabled: bool) {
+        self.evil_mode = enabled;
+    }
+
+    /// Returns whether evil mode is enabled
+    pub fn evil_mode(&self) -> bool {
+        self.evil_mode
+    }
+}
+
+// === TESTS ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn worst_spawn_picks_the_spot_that_blocks_merges() {
+        // A 2 or 4 at the top-left would pair with a neighbor; the bottom-right
+        // cell only touches 64 and 4096, so evil mode fills it instead
+        let mut game = GameState::from_grid([
+            [0, 2, 8, 16],
+            [4, 32, 64, 128],
+            [256, 512, 1024, 4096],
+            [8, 16, 64, 0],
+        ]);
+        game.worst_spawn();
+        assert_eq!(game.grid[0][0], 0);
+        assert!(matches!(game.grid[3][3], 2 | 4));
+        assert_eq!(game.spawned_cells(), &[(3, 3)]);
+    }
+
+    #[test]
+    fn corner_strategy_keeps_the_max_tile_in_its_corner() {
+        // Right or Down would drag the 64 out of the top-left corner
+        let game = GameState::from_grid([[64, 0, 8, 2], [16, 4, 0, 0], [0; 4], [4, 0, 2, 0]]);
+        let direction = game.best_move(AiStrategy::Corner).unwrap();
+        assert!(
+            matches!(direction, Direction::Up | Direction::Left),
+            "{:?}",
+            direction
+        );
+        let (grid, _) = game.simulate(direction).unwrap();
+        assert_eq!(grid[0][0], 64);
+    }
+
+    #[test]
+    fn expectimax_finds_the_winning_merge_and_matches_greedy_at_depth_zero() {
+        let near_solved = GameState::from_grid([
+            [1024, 1024, 512, 256],
+            [2, 4, 8, 16],
+            [0, 0, 0, 0],
+            [0, 0, 0, 2],
+        ]);
+        let direction = near_solved.expectimax_move(3).unwrap();
+        assert!(
+            matches!(direction, Direction::Left | Direction::Right),
+            "{:?}",
+            direction
+        );
+        let (grid, _) = near_solved.simulate(direction).unwrap();
+        assert!(grid.iter().flatten().any(|&cell| cell == 2048));
+
+        for grid in [
+            [[2, 0, 4, 0], [0, 8, 0, 2], [16, 0, 0, 4], [0, 2, 0, 0]],
+            [[2, 4, 8, 16], [4, 8, 16, 32], [0, 0, 2, 0], [0; 4]],
+        ] {
+            let game = GameState::from_grid(grid);
+            assert_eq!(game.expectimax_move(0), game.best_move(AiStrategy::Greedy));
+        }
+    }
+
+    #[test]
+    fn snake_ordered_board_is_more_monotonic_than_a_shuffled_one() {
+        let snake =
+            GameState::from_grid([[1024, 512, 256, 128], [8, 16, 32, 64], [4, 2, 0, 0], [0; 4]]);
+        let shuffled =
+            GameState::from_grid([[8, 1024, 2, 128], [512, 0, 32, 4], [64, 256, 0, 16], [0; 4]]);
+        assert!(snake.monotonicity() > shuffled.monotonicity());
+        assert!(snake.monotonicity() <= 0.0);
+    }
+
+    #[test]
+    fn equal_tiles_are_perfectly_smooth_and_alternating_tiles_are_not() {
+        let equal = GameState::from_grid([[8; 4]; 4]);
+        let alternating =
+            GameState::from_grid([[2, 4, 2, 4], [4, 2, 4, 2], [2, 4, 2, 4], [4, 2, 4, 2]]);
+        assert_eq!(equal.smoothness(), 0.0);
+        assert!(alternating.smoothness() < equal.smoothness());
+    }
+
+    #[test]
+    fn move_filling_the_last_useful_cell_is_flagged_as_risky() {
+        // Left leaves only the top-right cell open, next to an 8 and a 128, so a
+        // spawned 2 or 4 there ends the game
+        let game = GameState::from_grid([
+            [0, 2, 4, 8],
+            [16, 32, 64, 128],
+            [2, 4, 8, 16],
+            [32, 64, 128, 256],
+        ]);
+        assert!(game.move_leads_to_game_over(Direction::Left));
+        assert!(!game.move_leads_to_game_over(Direction::Right));
+
+        let open = GameState::from_grid([[2, 2, 0, 0], [0; 4], [0; 4], [0; 4]]);
+        assert!(!open.move_leads_to_game_over(Direction::Left));
+    }
+
+    #[test]
+    fn score_gain_previews_a_move_without_applying_it() {
+        let game = GameState::from_grid([[8, 8, 4, 4], [0; 4], [0; 4], [0; 4]]);
+        assert_eq!(game.score_gain(Direction::Left), 24);
+        assert_eq!(game.score_gain(Direction::Up), 0);
+        assert_eq!(game.grid[0], [8, 8, 4, 4]);
+        assert_eq!(game.score(), 0);
+    }
+
+    #[test]
+    fn adjacent_pairs_counts_every_mergeable_neighbor() {
+        // Row pairs (0,0)-(0,1), (0,1)-(0,2), (1,2)-(1,3) and column pairs
+        // (0,0)-(1,0), (1,1)-(2,1), (1,3)-(2,3)
+        let game = GameState::from_grid([[2, 2, 2, 0], [2, 4, 8, 8], [0, 4, 0, 8], [0; 4]]);
+        assert_eq!(game.adjacent_pairs(), 6);
+        assert_eq!(GameState::<4>::new_blank().adjacent_pairs(), 0);
+    }
+
+    #[test]
+    fn auto_arrange_demo_plays_a_short_valid_line() {
+        let mut game = GameState::<4>::new();
+        game.reset_with_seed(2);
+        let moves = game.auto_arrange_demo();
+        assert!(!moves.is_empty() && moves.len() <= DEMO_MOVE_LIMIT);
+        assert_eq!(game.moves() as usize, moves.len());
+        game.check_invariants().unwrap();
+    }
+
+    #[test]
+    fn solve_toward_finds_a_short_line_on_a_near_solved_board() {
+        let game = GameState::from_grid([[1024, 512, 256, 128], [0, 0, 0, 128], [0; 4], [0; 4]]);
+        let line = game.solve_toward(2048, 6).unwrap();
+        assert!(line.len() <= 5, "{:?}", line);
+
+        let mut replay = GameState::from_grid(game.grid);
+        for &direction in &line {
+            assert!(replay.move_no_spawn(direction));
+        }
+        assert!(replay.max_tile() >= 2048);
+        assert_eq!(game.solve_toward(2048, 1), None);
+    }
+
+    #[test]
+    fn invariants_hold_after_random_play() {
+        for seed in 0..30 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut game = GameState::<4>::new();
+            game.reset_with_seed(seed);
+            game.play_random(200, &mut rng);
+            game.check_invariants().unwrap();
+            assert!(game.moves() > 0);
+        }
+    }
+
+    #[test]
+    fn simulate_random_game_is_reproducible_from_its_seed() {
+        let first = GameState::<4>::simulate_random_game(42);
+        assert_eq!(GameState::<4>::simulate_random_game(42), first);
+        assert!(first.moves > 0);
+        assert!(first.score > 0);
+        assert_eq!(first.duration_secs, 0);
+    }
+
+    #[test]
+    fn fragmentation_scores_a_checkerboard_above_a_packed_row() {
+        let packed = GameState::<4>::from_grid([[2, 4, 8, 16], [0; 4], [0; 4], [0; 4]]);
+        assert_eq!(packed.fragmentation(), 4.0 / 24.0);
+
+        let checkerboard =
+            GameState::<4>::from_grid([[2, 0, 2, 0], [0, 2, 0, 2], [2, 0, 2, 0], [0, 2, 0, 2]]);
+        assert_eq!(checkerboard.fragmentation(), 1.0);
+
+        assert_eq!(GameState::<4>::from_grid([[0; 4]; 4]).fragmentation(), 0.0);
+        assert_eq!(GameState::<4>::from_grid([[2; 4]; 4]).fragmentation(), 0.0);
+    }
+
+    #[test]
+    fn realistic_board_lands_just_past_the_target_density() {
+        for seed in 0..20 {
+            for target in [0.25, 0.5, 0.75] {
+                let game = GameState::<4>::realistic_board(seed, target);
+                let density = game.density();
+                assert!(
+                    game.game_over || (density >= target && density < target + 0.13),
+                    "seed {} target {} density {}",
+                    seed,
+                    target,
+                    density
+                );
+                assert_eq!(
+                    GameState::<4>::realistic_board(seed, target).grid,
+                    game.grid
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn an_empty_board_estimates_more_remaining_moves_than_a_crowded_one() {
+        let empty = GameState::<4>::from_grid([[2, 0, 0, 0], [0; 4], [0; 4], [0; 4]]);
+        let crowded = GameState::<4>::from_grid([
+            [2, 4, 8, 16],
+            [32, 64, 128, 256],
+            [2, 4, 8, 16],
+            [0, 0, 2, 4],
+        ]);
+        let roomy = empty.estimate_remaining_moves(30, 1);
+        assert!(roomy > crowded.estimate_remaining_moves(30, 1));
+        assert_eq!(empty.estimate_remaining_moves(30, 1), roomy);
+        assert_eq!(empty.estimate_remaining_moves(0, 1), 0.0);
+
+        // The rollouts run on copies and leave the game as it was
+        assert_eq!(empty.moves, 0);
+        assert_eq!(empty.grid[0], [2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn simulation_copy_plays_like_the_original() {
+        let mut game = GameState::<4>::realistic_board(3, 0.5);
+        game.set_merge_pass(crate::MergePass::SinglePass);
+        let mut copy = game.simulation_copy();
+        assert_eq!(copy.grid, game.grid);
+        assert_eq!(copy.score, game.score);
+
+        for direction in [
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+        ] {
+            assert_eq!(copy.apply_move(direction), game.apply_move(direction));
+            assert_eq!(copy.grid, game.grid);
+            assert_eq!(copy.score, game.score);
+        }
+    }
+
+    #[test]
+    fn expectimax_chance_nodes_follow_the_spawn_range() {
+        let mut game = GameState::<4>::new();
+        assert_eq!(game.spawn_distribution(), vec![(2, 0.9), (4, 0.1)]);
+        game.set_spawn_exponent_range(3, 3).unwrap();
+        assert_eq!(game.spawn_distribution(), vec![(8, 1.0)]);
+
+        // With only 8s spawning, the single empty cell can only receive an 8
+        let grid = [[2, 4, 2, 4], [4, 2, 4, 2], [2, 4, 2, 4], [4, 2, 16, 0]];
+        let spawns = game.spawn_distribution();
+        let mut search = Expectimax {
+            game: &game,
+            rules: game.merge_rules(),
+            spawns: &spawns,
+            table: HashMap::new(),
+        };
+        let mut with_eight = grid;
+        with_eight[3][3] = 8;
+        let expected = search.max_value(&with_eight, 0);
+        assert_eq!(search.chance_value(&grid, 1), expected);
+    }
+
+    #[test]
+    fn expectimax_moves_skip_directions_blocked_by_row_practice() {
+        let mut game = GameState::<4>::new();
+        game.start_row_practice(1).unwrap();
+
+        // Only a vertical slide could move this row, and those are refused
+        let grid = [[0; 4], [2, 4, 8, 16], [0; 4], [0; 4]];
+        let spawns = game.spawn_distribution();
+        let mut search = Expectimax {
+            game: &game,
+            rules: game.merge_rules(),
+            spawns: &spawns,
+            table: HashMap::new(),
+        };
+        assert_eq!(
+            search.max_value(&grid, 1),
+            evaluate_grid(&grid) - GAME_OVER_PENALTY
+        );
+    }
+
+    #[test]
+    fn expectimax_spawns_skip_cells_blocked_by_row_practice() {
+        let mut game = GameState::<4>::new();
+        game.start_row_practice(1).unwrap();
+        game.set_spawn_exponent_range(3, 3).unwrap();
+
+        // The last cell of the row is the only one a tile can spawn in
+        let grid = [[0; 4], [2, 4, 8, 0], [0; 4], [0; 4]];
+        let spawns = game.spawn_distribution();
+        let mut search = Expectimax {
+            game: &game,
+            rules: game.merge_rules(),
+            spawns: &spawns,
+            table: HashMap::new(),
+        };
+        let mut with_eight = grid;
+        with_eight[1][3] = 8;
+        let expected = search.max_value(&with_eight, 0);
+        assert_eq!(search.chance_value(&grid, 1), expected);
+    }
+
+    #[test]
+    fn solve_toward_only_suggests_moves_row_practice_allows() {
+        let mut game = GameState::<4>::new();
+        game.start_row_practice(2).unwrap();
+        game.grid = [[0; 4], [0; 4], [4, 2, 0, 2], [0; 4]];
+
+        let line = game.solve_toward(8, 4).unwrap();
+        assert!(line
+            .iter()
+            .all(|&direction| matches!(direction, Direction::Left | Direction::Right)));
+        for &direction in &line {
+            assert!(game.move_no_spawn(direction));
+        }
+        assert!(game.max_tile() >= 8);
+    }
+}
@@ -0,0 +1,62 @@
+// 2048 Game - Terminal Entry Point
+//
+// A text version of the game for machines without a GPU or a display. It reuses
+// the same GameState as the ggez front end: moves are read from stdin as WASD
+// keys and the board is printed with render_ascii() after every turn.
+//
+// Build and run with `cargo run --features cli --bin rust_2048_cli`.
+
+use rust_2048_game::{Direction, GameState};
+use std::io::{self, BufRead, Write};
+
+/// Main function that runs the terminal play loop
+///
+/// Each line of input may hold several keys, which are applied in order:
+/// w/a/s/d move the tiles up/left/down/right and q quits. Other characters are
+/// ignored. The loop ends at game over, on q, or when stdin is closed, and
+/// prints the game's statistics before exiting.
+///
+/// # Returns
+///
+/// * `io::Result<()>` - Err if reading stdin or writing stdout fails
+fn main() -> io::Result<()> {
+    let mut state = GameState::new();
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    print!("{}", state.render_ascii());
+    'play: while !state.is_game_over() {
+        print!("Move (w/a/s/d, q to quit): ");
+        stdout.flush()?;
+
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break; // stdin closed
+        }
+
+        for key in line.chars() {
+            if key.eq_ignore_ascii_case(&'q') {
+                break 'play;
+            }
+            if let Some(direction) = Direction::from_key(key) {
+                state.apply_move(direction);
+                if state.is_game_over() {
+                    break;
+                }
+            }
+        }
+        print!("{}", state.render_ascii());
+    }
+
+    // === GAME SUMMARY ===
+    let stats = state.stats();
+    if state.is_game_over() {
+        println!("Game over!");
+    }
+    println!("Score: {}", stats.score);
+    println!("Moves: {}", stats.moves);
+    println!("Merges: {}", stats.merges);
+    println!("Highest tile: {}", stats.max_tile);
+    println!("Time: {}s", stats.duration_secs);
+    Ok(())
+}
@@ -0,0 +1,225 @@
+// 2048 Game Key Bindings - Mapping Keys to Game Actions
+//
+// key_down_event() resolves every pressed key through a KeyBindings map instead
+// of matching key codes directly, so controls can be rebound, e.g. to WASD or
+// for AZERTY and Dvorak layouts:
+// - KeyAction, every action a key can trigger
+// - KeyBindings, the map from keys to actions, defaulting to the classic keys
+//
+// Context-specific keys stay fixed: Esc leaves the history viewer, Shift turns
+// an arrow into a move preview, and playback uses Space, +/- and Esc.
+
+use crate::{Direction, GameState};
+use ggez::input::keyboard::KeyCode;
+use std::collections::HashMap;
+
+// === KEY ACTIONS ===
+
+/// Everything a bound key can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    /// Move the tiles up
+    MoveUp,
+    /// Move the tiles down
+    MoveDown,
+    /// Move the tiles left
+    MoveLeft,
+    /// Move the tiles right
+    MoveRight,
+    /// Restart mid-game, confirmed by a second press
+    Restart,
+    /// Start a new game after a game over
+    NewGame,
+    /// Take back the last move
+    Undo,
+    /// Pause or resume
+    Pause,
+    /// Switch to the next built-in palette
+    CyclePalette,
+    /// Spend a revive after a game over
+    Revive,
+    /// Save a screenshot
+    Screenshot,
+    /// Step backward through the move history
+    HistoryBack,
+    /// Step forward through the move history
+    HistoryForward,
+    /// Jump to the first recorded board
+    HistoryStart,
+    /// Jump to the latest recorded board
+    HistoryEnd,
+}
+
+impl KeyAction {
+    /// Returns the move direction of a movement action
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Direction>` - The direction, or None for non-movement actions
+    pub fn direction(self) -> Option<Direction> {
+        match self {
+            KeyAction::MoveUp => Some(Direction::Up),
+            KeyAction::MoveDown => Some(Direction::Down),
+            KeyAction::MoveLeft => Some(Direction::Left),
+            KeyAction::MoveRight => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+// === KEY BINDINGS ===
+
+/// Map from keys to the actions they trigger
+///
+/// A key triggers at most one action, while an action may have several keys.
+/// `KeyBindings::default()` matches the controls listed in the README.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    /// Action of every bound key
+    actions: HashMap<KeyCode, KeyAction>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let actions = [
+            (KeyCode::Up, KeyAction::MoveUp),
+            (KeyCode::Down, KeyAction::MoveDown),
+            (KeyCode::Left, KeyAction::MoveLeft),
+            (KeyCode::Right, KeyAction::MoveRight),
+            (KeyCode::R, KeyAction::Restart),
+            (KeyCode::Return, KeyAction::NewGame),
+            (KeyCode::U, KeyAction::Undo),
+            (KeyCode::P, KeyAction::Pause),
+            (KeyCode::T, KeyAction::CyclePalette),
+            (KeyCode::V, KeyAction::Revive),
+            (KeyCode::F12, KeyAction::Screenshot),
+            (KeyCode::LBracket, KeyAction::HistoryBack),
+            (KeyCode::RBracket, KeyAction::HistoryForward),
+            (KeyCode::Home, KeyAction::HistoryStart),
+            (KeyCode::PageUp, KeyAction::HistoryStart),
+            (KeyCode::End, KeyAction::HistoryEnd),
+            (KeyCode::PageDown, KeyAction::HistoryEnd),
+        ];
+        KeyBindings {
+            actions: actions.into_iter().collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Creates a map with no key bound, for games that never read input
+    pub(crate) fn unbound() -> Self {
+        KeyBindings {
+            actions: HashMap::new(),
+        }
+    }
+
+    /// Makes `key` the only key for `action`
+    ///
+    /// The action's previous keys are unbound, and if `key` was bound to another
+    /// action it now triggers `action` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action to rebind
+    /// * `key` - The key that should trigger it
+    pub fn bind(&mut self, action: KeyAction, key: KeyCode) {
+        self.actions.retain(|_, bound| *bound != action);
+        self.actions.insert(key, action);
+    }
+
+    /// Adds another key for `action`, keeping the keys it already has
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action to add a key to
+    /// * `key` - The extra key; unbound from any other action
+    pub fn add_key(&mut self, action: KeyAction, key: KeyCode) {
+        self.actions.insert(key, action);
+    }
+
+    /// Removes a key from the map, so it no longer triggers anything
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to unbind
+    pub fn unbind(&mut self, key: KeyCode) {
+        self.actions.remove(&key);
+    }
+
+    /// Looks up the action a key triggers
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The pressed key
+    ///
+    /// # Returns
+    ///
+    /// * `Option<KeyAction>` - The bound action, or None for an unbound key
+    pub fn action_for(&self, key: KeyCode) -> Option<KeyAction> {
+        self.actions.get(&key).copied()
+    }
+
+    /// Lists the keys bound to an action
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action to look up
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<KeyCode>` - Every key that triggers the action, in no particular order
+    pub fn keys_for(&self, action: KeyAction) -> Vec<KeyCode> {
+        self.actions
+            .iter()
+            .filter(|&(_, &bound)| bound == action)
+            .map(|(&key, _)| key)
+            .collect()
+    }
+}
+
+// === BINDING SELECTION ===
+
+impl<const N: usize> GameState<N> {
+    /// Replaces the key bindings used by key_down_event()
+    ///
+    /// # Arguments
+    ///
+    /// * `bindings` - The new map from keys to actions
+    pub fn set_bindings(&mut self, bindings: KeyBindings) {
+        self.key_bindings = bindings;
+    }
+
+    /// Returns the key bindings used by key_down_event()
+    pub fn bindings(&self) -> &KeyBindings {
+        &self.key_bindings
+    }
+}
+
+// === TESTS ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebinding_move_up_routes_the_new_key_to_an_up_move() {
+        let mut bindings = KeyBindings::default();
+        bindings.bind(KeyAction::MoveUp, KeyCode::W);
+
+        let mut game = GameState::<4>::new();
+        game.set_bindings(bindings);
+        let route = |key| {
+            game.bindings()
+                .action_for(key)
+                .and_then(KeyAction::direction)
+        };
+        assert_eq!(route(KeyCode::W), Some(Direction::Up));
+        assert_eq!(route(KeyCode::Up), None);
+        assert_eq!(route(KeyCode::Down), Some(Direction::Down));
+        assert_eq!(
+            game.bindings().keys_for(KeyAction::MoveUp),
+            vec![KeyCode::W]
+        );
+    }
+}
@@ -0,0 +1,969 @@
+// 2048 Game Board Utilities - Sliding, Packing and Symmetry
+//
+// This module provides pure transformations of a Grid that do not depend on the
+// rest of the game state:
+// - The slide-and-merge algorithm for each direction, on a bare grid so it can
+//   be used both for real moves and for AI simulation
+// - Packing a board into a single u64 (4 bits per cell) for hashing and AI tables
+// - The 8 dihedral symmetries of a square board (rotations and reflections)
+// - Equality and hashing of game states by board, for any board size
+//
+// Sliding and symmetry work on boards of any size N; packing only fits the
+// classic 4x4 board, since 16 cells of 4 bits fill a u64 exactly.
+
+use crate::{Direction, GameState, Grid, MergePass, MergePreview, GRID_SIZE};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+// === SLIDING AND MERGING ===
+
+/// Summary of what a single slide did to a grid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Slide {
+    /// Whether any tile moved or merged
+    pub(crate) moved: bool,
+    /// Points earned, i.e. the sum of the values of all newly merged tiles
+    pub(crate) gained: u32,
+    /// Number of merges performed
+    pub(crate) merges: u32,
+    /// Largest tile value created by a merge (0 if nothing merged)
+    pub(crate) biggest_merge: u32,
+    /// Points earned under combo scoring, where the k-th merge of the slide
+    /// counts (k + 1) / 2 times its value
+    pub(crate) combo_gained: u32,
+}
+
+impl Slide {
+    /// Records a merge that produced a tile of the given value
+    fn record_merge(&mut self, value: u32) {
+        self.merges += 1;
+        self.gained = self.gained.saturating_add(value);
+        let weighted = value.saturating_mul(self.merges + 1) / 2;
+        self.combo_gained = self.combo_gained.saturating_add(weighted);
+        self.biggest_merge = self.biggest_merge.max(value);
+    }
+}
+
+/// Rules that control how equal tiles merge during a slide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MergeRules {
+    /// Factor applied to a tile's value when two equal tiles merge
+    pub(crate) multiplier: u32,
+    /// Whether a tile created by a merge may merge again in the same move
+    pub(crate) pass: MergePass,
+}
+
+impl MergeRules {
+    /// Returns whether a tile may absorb a matching neighbor, given whether it
+    /// was already produced by a merge during this move
+    fn can_merge(self, already_merged: bool) -> bool {
+        !already_merged || self.pass == MergePass::SinglePass
+    }
+
+    /// Returns the value of the tile two tiles of `value` merge into, capped at
+    /// u32::MAX so that a large multiplier can never overflow
+    pub(crate) fn merged_value(self, value: u32) -> u32 {
+        value.saturating_mul(self.multiplier)
+    }
+}
+
+/// Slides a grid in the given direction without spawning a tile
+///
+/// # Arguments
+///
+/// * `grid` - The board to modify in place
+/// * `direction` - The direction to move tiles
+/// * `rules` - The merge multiplier and merge pass to apply
+///
+/// # Returns
+///
+/// * `Slide` - Whether anything moved, plus the merges and points produced
+pub(crate) fn slide<const N: usize>(
+    grid: &mut Grid<N>,
+    direction: Direction,
+    rules: MergeRules,
+) -> Slide {
+    match direction {
+        Direction::Up => slide_up(grid, rules),
+        Direction::Down => slide_down(grid, rules),
+        Direction::Left => slide_left(grid, rules),
+        Direction::Right => slide_right(grid, rules),
+    }
+}
+
+/// Slides every tile right and merges identical adjacent tiles
+///
+/// This is the core 2048 movement algorithm:
+/// 1. Processes each row from right to left (reverse order)
+/// 2. For each non-empty tile, slides it as far right as possible
+/// 3. Merges tiles with identical values when they collide, multiplying the
+///    value by the rules' multiplier
+/// 4. Ensures each tile can only merge once per move (unless the rules use
+///    MergePass::SinglePass)
+///
+/// A "merged" tracking array prevents tiles from merging multiple times in a
+/// single move, which is crucial for correct 2048 gameplay: `[4, 4, 4, 4]`
+/// moved right becomes `[0, 0, 8, 8]`, never a single 16. The guard is kept
+/// per cell of the whole grid, so slide_up() and slide_down() apply it to
+/// columns in exactly the same way.
+///
+/// Because the tile nearest the destination wall is handled first, pairs form
+/// from that wall outwards, exactly as in the original game: moving right,
+/// `[2, 2, 2, 2]` becomes `[0, 0, 4, 4]`, `[4, 4, 2, 2]` becomes `[0, 0, 8, 4]`,
+/// `[4, 2, 2, 0]` becomes `[0, 0, 4, 4]` and `[2, 2, 4, 0]` becomes
+/// `[0, 0, 4, 4]`. Sliding is interleaved with merging, but a tile stops at
+/// the first occupied cell, so it can never jump over a tile to merge. Every
+/// row of four tiles drawn from 0, 2, 4 and 8 produces the same result as
+/// compacting the row and merging equal neighbors pairwise from the wall, in
+/// all four directions.
+pub(crate) fn slide_right<const N: usize>(grid: &mut Grid<N>, rules: MergeRules) -> Slide {
+    let mut slide = Slide::default();
+    // Track which cells have already merged this turn to prevent double-merging
+    let mut merged = [[false; N]; N];
+
+    // Process each row
+    for i in 0..N {
+        // Process columns from right to left (reverse order)
+        // This ensures tiles slide as far right as possible
+        for j in (0..N - 1).rev() {
+            if grid[i][j] != 0 {
+                let mut col = j;
+
+                // Slide the tile as far right as possible
+                while col < N - 1 {
+                    // Case 1: Empty cell to the right - slide the tile
+                    if grid[i][col + 1] == 0 {
+                        grid[i][col + 1] = grid[i][col];
+                        grid[i][col] = 0;
+                        slide.moved = true;
+                        col += 1;
+                    }
+                    // Case 2: Matching tile to the right that hasn't merged yet - merge them
+                    else if grid[i][col + 1] == grid[i][col]
+                        && rules.can_merge(merged[i][col + 1])
+                    {
+                        grid[i][col + 1] = rules.merged_value(grid[i][col + 1]); // Multiply
+                        grid[i][col] = 0; // Remove the original tile
+                        merged[i][col + 1] = true; // Mark as merged to prevent double-merging
+                        slide.record_merge(grid[i][col + 1]); // Count the merge and its points
+                        slide.moved = true;
+                        break; // Stop sliding this tile
+                    }
+                    // Case 3: Different tile or already merged - stop sliding
+                    else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    slide
+}
+
+/// Slides every tile left and merges identical adjacent tiles
+///
+/// Implements the same sliding and merging algorithm as slide_right()
+/// but processes columns from left to right instead.
+pub(crate) fn slide_left<const N: usize>(grid: &mut Grid<N>, rules: MergeRules) -> Slide {
+    let mut slide = Slide::default();
+    let mut merged = [[false; N]; N];
+
+    // move left
+    for i in 0..N {
+        for j in 1..N {
+            // if the cell is not empty
+            if grid[i][j] != 0 {
+                let mut col = j;
+                // move left
+                while col > 0 {
+                    // if the cell to the left is empty
+                    if grid[i][col - 1] == 0 {
+                        grid[i][col - 1] = grid[i][col]; // move the tile to the left
+                        grid[i][col] = 0; // set the current cell to 0
+                        slide.moved = true; // set the moved flag to true
+                        col -= 1; // move the column to the left
+                    }
+                    // merge tiles
+                    else if grid[i][col - 1] == grid[i][col]
+                        && rules.can_merge(merged[i][col - 1])
+                    // if the cell to the left is not merged
+                    {
+                        grid[i][col - 1] = rules.merged_value(grid[i][col - 1]); // merge tiles
+                        grid[i][col] = 0; // set the current cell to 0
+                        merged[i][col - 1] = true; // set the merged cell to true
+                        slide.record_merge(grid[i][col - 1]); // count the merge and its points
+                        slide.moved = true; // set the moved flag to true
+                        break; // break the loop
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    slide
+}
+
+/// Slides every tile up and merges identical adjacent tiles
+///
+/// Implements the same sliding and merging algorithm as slide_right()
+/// but processes rows from top to bottom instead.
+pub(crate) fn slide_up<const N: usize>(grid: &mut Grid<N>, rules: MergeRules) -> Slide {
+    let mut slide = Slide::default();
+    let mut merged = [[false; N]; N];
+
+    for j in 0..N {
+        for i in 1..N {
+            // if the cell is not empty
+            if grid[i][j] != 0 {
+                let mut row = i;
+                // move up
+                while row > 0 {
+                    // if the cell above is empty
+                    if grid[row - 1][j] == 0 {
+                        // move the tile up
+                        grid[row - 1][j] = grid[row][j];
+                        grid[row][j] = 0; // set the current cell to 0
+                        slide.moved = true; // set the moved flag to true
+                        row -= 1; // move the row up
+                    }
+                    // merge tiles
+                    else if grid[row - 1][j] == grid[row][j]
+                        && rules.can_merge(merged[row - 1][j])
+                    {
+                        grid[row - 1][j] = rules.merged_value(grid[row - 1][j]); // merge tiles
+                        grid[row][j] = 0; // set the current cell to 0
+                        merged[row - 1][j] = true; // set the merged cell to true
+                        slide.record_merge(grid[row - 1][j]); // count the merge and its points
+                        slide.moved = true; // set the moved flag to true
+                        break; // break the loop
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    slide
+}
+
+/// Slides every tile down and merges identical adjacent tiles
+///
+/// Implements the same sliding and merging algorithm as slide_right()
+/// but processes rows from bottom to top instead.
+pub(crate) fn slide_down<const N: usize>(grid: &mut Grid<N>, rules: MergeRules) -> Slide {
+    let mut slide = Slide::default();
+    let mut merged = [[false; N]; N];
+
+    for j in 0..N {
+        for i in (0..N - 1).rev() {
+            // if the cell is not empty
+            if grid[i][j] != 0 {
+                let mut row = i;
+                // move down
+                while row < N - 1 {
+                    // if the cell below is empty
+                    if grid[row + 1][j] == 0 {
+                        grid[row + 1][j] = grid[row][j]; // move the tile down
+                        grid[row][j] = 0; // set the current cell to 0
+                        slide.moved = true; // set the moved flag to true
+                        row += 1; // move the row down
+                    }
+                    // merge tiles
+                    else if grid[row + 1][j] == grid[row][j]
+                        && rules.can_merge(merged[row + 1][j])
+                    {
+                        grid[row + 1][j] = rules.merged_value(grid[row + 1][j]); // merge tiles
+                        grid[row][j] = 0; // set the current cell to 0
+                        merged[row + 1][j] = true; // set the merged cell to true
+                        slide.record_merge(grid[row + 1][j]); // count the merge and its points
+                        slide.moved = true; // set the moved flag to true
+                        break; // break the loop
+                    } else {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    slide
+}
+
+// === MERGE PREVIEW ===
+
+/// Returns the cells of one row or column, ordered from the edge tiles move
+/// toward
+///
+/// # Arguments
+///
+/// * `direction` - The direction of the move
+/// * `line` - Row index for Left/Right, column index for Up/Down
+fn line_cells<const N: usize>(direction: Direction, line: usize) -> [(usize, usize); N] {
+    std::array::from_fn(|k| match direction {
+        Direction::Left => (line, k),
+        Direction::Right => (line, N - 1 - k),
+        Direction::Up => (k, line),
+        Direction::Down => (N - 1 - k, line),
+    })
+}
+
+/// Slides a grid like slide(), also reporting where every tile went
+///
+/// The tiles are walked once per line and the new board is built from their
+/// destinations, so the result matches slide() without running it.
+///
+/// # Arguments
+///
+/// * `grid` - The board to modify in place
+/// * `direction` - The direction to move tiles
+/// * `rules` - The merge multiplier and merge pass to apply
+///
+/// # Returns
+///
+/// * `(Slide, Vec<MergePreview>)` - The summary slide() would return, plus one
+///   `(source, destination, value)` entry per tile as listed by move_preview()
+pub(crate) fn slide_with_moves<const N: usize>(
+    grid: &mut Grid<N>,
+    direction: Direction,
+    rules: MergeRules,
+) -> (Slide, Vec<MergePreview>) {
+    let mut slide = Slide::default();
+    let mut moves: Vec<MergePreview> = Vec::new();
+
+    for line in 0..N {
+        let cells = line_cells::<N>(direction, line);
+        let mut next_slot = 0;
+        // Last placed tile that can still absorb a merge, with the index of
+        // its entry in `moves`
+        let mut open: Option<(u32, usize, usize)> = None;
+
+        for &(i, j) in &cells {
+            let value = grid[i][j];
+            if value == 0 {
+                continue;
+            }
+            match open {
+                Some((open_value, slot, entry)) if open_value == value => {
+                    let merged = rules.merged_value(value);
+                    slide.record_merge(merged);
+                    moves.push(((i, j), cells[slot], merged));
+                    // Every tile already in the merged tile takes the new value
+                    for earlier in &mut moves[entry..] {
+                        if earlier.1 == cells[slot] {
+                            earlier.2 = merged;
+                        }
+                    }
+                    open = match rules.pass {
+                        MergePass::Strict2048 => None,
+                        MergePass::SinglePass => Some((merged, slot, entry)),
+                    };
+                }
+                _ => {
+                    open = Some((value, next_slot, moves.len()));
+                    moves.push(((i, j), cells[next_slot], value));
+                    next_slot += 1;
+                }
+            }
+        }
+    }
+
+    *grid = [[0; N]; N];
+    for &(from, (i, j), value) in &moves {
+        grid[i][j] = value;
+        slide.moved |= from != (i, j);
+    }
+    (slide, moves)
+}
+
+impl<const N: usize> GameState<N> {
+    /// Lists the merges a move would perform, without changing the board
+    ///
+    /// Each entry is `(source, target, value)`: the tile at `source` slides into
+    /// the matching tile that ends up at `target`, producing a tile of `value`.
+    /// Cells are `(row, col)`. Tiles pair up from the edge they move toward,
+    /// following the game's merge pass exactly as move_tiles() does, so with the
+    /// default rules `[2, 2, 2, 0]` moved left reports only the two leftmost 2s
+    /// merging into column 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The move to preview
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<MergePreview>` - The merges in row-major order for Left/Right and
+    ///   column-major order for Up/Down; empty if the move merges nothing
+    pub fn merge_preview(&self, direction: Direction) -> Vec<MergePreview> {
+        let rules = self.merge_rules();
+        let mut merges = Vec::new();
+
+        for line in 0..N {
+            let cells = line_cells::<N>(direction, line);
+            // Next free slot along the line, and the last placed tile that can
+            // still absorb a merge
+            let mut next_slot = 0;
+            let mut open: Option<(u32, usize)> = None;
+
+            for &(i, j) in &cells {
+                let value = self.grid[i][j];
+                if value == 0 {
+                    continue;
+                }
+                match open {
+                    Some((open_value, slot)) if open_value == value => {
+                        let merged = rules.merged_value(value);
+                        merges.push(((i, j), cells[slot], merged));
+                        // Under SinglePass the new tile can absorb the next match too
+                        open = match self.merge_pass {
+                            MergePass::Strict2048 => None,
+                            MergePass::SinglePass => Some((merged, slot)),
+                        };
+                    }
+                    _ => {
+                        open = Some((value, next_slot));
+                        next_slot += 1;
+                    }
+                }
+            }
+        }
+
+        merges
+    }
+
+    /// Lists where every tile would land after a move, without changing the board
+    ///
+    /// Each entry is `(source, destination, value)` for one tile now on the
+    /// board, where `value` is the tile's value once it lands: both tiles of a
+    /// merge report the merged value and the same destination. Destinations
+    /// follow the game's merge pass exactly as merge_preview() does. Used to draw
+    /// the ghost preview while Shift is held.
+    ///
+    /// # Arguments
+    ///
+    /// * `direction` - The move to preview
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<MergePreview>` - One entry per tile, in the same order as
+    ///   merge_preview(); tiles that stay put have equal source and destination
+    pub fn move_preview(&self, direction: Direction) -> Vec<MergePreview> {
+        let mut grid = self.grid;
+        slide_with_moves(&mut grid, direction, self.merge_rules()).1
+    }
+}
+
+// === MOVE VALIDATION ===
+
+impl<const N: usize> GameState<N> {
+    /// Checks whether `after` can follow `before` by exactly one legal turn
+    ///
+    /// A legal turn is a move that changes the board followed by a single 2 or
+    /// 4 spawned in a cell the move left empty. Every direction is simulated
+    /// from `before` (using its merge rules) and `after` must match one of
+    /// the results except for that one new tile. Useful for validating states
+    /// submitted by an untrusted client.
+    ///
+    /// Only the boards are compared; score and other statistics are not checked.
+    ///
+    /// # Arguments
+    ///
+    /// * `before` - The state before the turn
+    /// * `after` - The claimed state after the turn
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if some move plus one spawn turns `before` into `after`
+    pub fn is_legal_successor(before: &GameState<N>, after: &GameState<N>) -> bool {
+        Direction::ALL.into_iter().any(|direction| {
+            let mut moved = before.grid;
+            if !slide(&mut moved, direction, before.merge_rules()).moved {
+                return false;
+            }
+
+            let mut spawned = 0;
+            for (moved_row, after_row) in moved.iter().zip(&after.grid) {
+                for (&old, &new) in moved_row.iter().zip(after_row) {
+                    if old == new {
+                        continue;
+                    }
+                    if old != 0 || (new != 2 && new != 4) {
+                        return false;
+                    }
+                    spawned += 1;
+                }
+            }
+            spawned == 1
+        })
+    }
+}
+
+// === PACKED REPRESENTATION ===
+
+/// Packs a grid into a u64 using 4 bits per cell
+///
+/// Each cell stores the base-2 exponent of its tile (0 for empty, 1 for 2,
+/// 2 for 4, ... 15 for 32768), row by row with the top-left cell in the most
+/// significant bits. Because of that ordering, comparing two packed values
+/// compares the boards cell by cell in reading order.
+///
+/// Only meaningful for the classic 4x4 board with power-of-two tiles; larger
+/// exponents are clamped to 15.
+pub(crate) fn pack_grid(grid: &Grid) -> u64 {
+    let mut packed = 0u64;
+    for row in grid {
+        for &cell in row {
+            let exponent = if cell == 0 {
+                0
+            } else {
+                cell.trailing_zeros().min(15) as u64
+            };
+            packed = (packed << 4) | exponent;
+        }
+    }
+    packed
+}
+
+/// Unpacks a u64 produced by pack_grid() back into a grid
+///
+/// Each 4-bit exponent e becomes the tile 2^e, with 0 as an empty cell.
+pub(crate) fn unpack_grid(packed: u64) -> Grid {
+    let mut grid = [[0; GRID_SIZE as usize]; GRID_SIZE as usize];
+    let mut shift = GRID_SIZE * GRID_SIZE * 4;
+    for row in grid.iter_mut() {
+        for cell in row.iter_mut() {
+            shift -= 4;
+            let exponent = (packed >> shift) & 0xF;
+            *cell = if exponent == 0 { 0 } else { 1 << exponent };
+        }
+    }
+    grid
+}
+
+// === SYMMETRY ===
+
+/// Returns the grid rotated 90° clockwise
+pub(crate) fn rotate_grid_cw<const N: usize>(grid: &Grid<N>) -> Grid<N> {
+    let mut rotated = [[0; N]; N];
+    for (i, row) in grid.iter().enumerate() {
+        for (j, &cell) in row.iter().enumerate() {
+            rotated[j][N - 1 - i] = cell;
+        }
+    }
+    rotated
+}
+
+/// Returns the grid rotated 90° counter-clockwise
+pub(crate) fn rotate_grid_ccw<const N: usize>(grid: &Grid<N>) -> Grid<N> {
+    let mut rotated = [[0; N]; N];
+    for (i, row) in grid.iter().enumerate() {
+        for (j, &cell) in row.iter().enumerate() {
+            rotated[N - 1 - j][i] = cell;
+        }
+    }
+    rotated
+}
+
+/// Returns the grid mirrored left-to-right (each row reversed)
+pub(crate) fn flip_grid_horizontal<const N: usize>(grid: &Grid<N>) -> Grid<N> {
+    let mut flipped = *grid;
+    for row in flipped.iter_mut() {
+        row.reverse();
+    }
+    flipped
+}
+
+/// Returns the grid mirrored top-to-bottom (row order reversed)
+pub(crate) fn flip_grid_vertical<const N: usize>(grid: &Grid<N>) -> Grid<N> {
+    let mut flipped = *grid;
+    flipped.reverse();
+    flipped
+}
+
+/// Returns all 8 dihedral symmetries of a grid
+///
+/// The first four entries are the grid rotated clockwise by 0°, 90°, 180°,
+/// and 270°; the last four are the same rotations of its horizontal mirror.
+pub(crate) fn symmetries<const N: usize>(grid: &Grid<N>) -> [Grid<N>; 8] {
+    let mut all = [*grid; 8];
+    for k in 1..4 {
+        all[k] = rotate_grid_cw(&all[k - 1]);
+    }
+    all[4] = flip_grid_horizontal(grid);
+    for k in 5..8 {
+        all[k] = rotate_grid_cw(&all[k - 1]);
+    }
+    all
+}
+
+/// Returns the representative of a grid's symmetry class
+///
+/// This is the smallest of the grid's 8 dihedral symmetries in row-by-row
+/// order, so two grids that are rotations or reflections of each other share
+/// the same representative. Unlike canonical_key(), it works for any board size
+/// and any tile values.
+pub(crate) fn canonical_grid<const N: usize>(grid: &Grid<N>) -> Grid<N> {
+    symmetries(grid).into_iter().min().unwrap_or(*grid)
+}
+
+/// Returns the smallest packed value among a grid's 8 dihedral symmetries
+pub(crate) fn canonical_key(grid: &Grid) -> u64 {
+    symmetries(grid).iter().map(pack_grid).min().unwrap_or(0)
+}
+
+impl<const N: usize> GameState<N> {
+    /// Rotates the board 90° clockwise in place
+    ///
+    /// The top row becomes the rightmost column, so the cell at `(row, col)`
+    /// moves to `(col, N - 1 - row)`. Four rotations restore the original board.
+    /// Boards are always square, so the dimensions never change.
+    ///
+    /// Only the live grid is rotated; the history keeps the boards as played.
+    pub fn rotate_cw(&mut self) {
+        self.grid = rotate_grid_cw(&self.grid);
+    }
+
+    /// Rotates the board 90° counter-clockwise in place
+    ///
+    /// The inverse of rotate_cw(): the top row becomes the leftmost column,
+    /// read from bottom to top.
+    pub fn rotate_ccw(&mut self) {
+        self.grid = rotate_grid_ccw(&self.grid);
+    }
+
+    /// Mirrors the board left-to-right in place, reversing every row
+    ///
+    /// Flipping twice restores the original board. A left move on the mirrored
+    /// board is a right move on the original, mirrored back.
+    pub fn flip_horizontal(&mut self) {
+        self.grid = flip_grid_horizontal(&self.grid);
+    }
+
+    /// Mirrors the board top-to-bottom in place, reversing the order of the rows
+    ///
+    /// Flipping twice restores the original board. An up move on the mirrored
+    /// board is a down move on the original, mirrored back.
+    pub fn flip_vertical(&mut self) {
+        self.grid = flip_grid_vertical(&self.grid);
+    }
+}
+
+// Packing is only defined for the classic 4x4 board
+impl GameState {
+    /// Creates a game state from a packed board, as returned by packed()
+    ///
+    /// Each hex digit of `packed` is one cell's exponent in reading order, so
+    /// `0x1000_0000_0000_0002` has a 2 in the top-left corner and a 4 in the
+    /// bottom-right. Handy for writing compact, deterministic fixtures.
+    ///
+    /// # Arguments
+    ///
+    /// * `packed` - The packed board
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A GameState holding the unpacked board, as from_grid() would
+    pub fn from_packed(packed: u64) -> Self {
+        GameState::from_grid(unpack_grid(packed))
+    }
+
+    /// Returns the board packed into a u64 (4 bits per cell)
+    ///
+    /// Each cell holds the base-2 exponent of its tile, so two boards pack to the
+    /// same value exactly when they hold the same tiles in the same places (for
+    /// tiles up to 32768).
+    pub fn packed(&self) -> u64 {
+        pack_grid(&self.grid)
+    }
+
+    /// Returns a key shared by every board equivalent to this one under rotation
+    /// or reflection
+    ///
+    /// The key is the smallest packed value among the board's 8 dihedral
+    /// symmetries. AI search can use it to collapse symmetric positions into a
+    /// single transposition table entry, since their best moves mirror each other.
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The canonical packed form of the board
+    pub fn canonical(&self) -> u64 {
+        canonical_key(&self.grid)
+    }
+}
+
+// === EQUALITY AND HASHING ===
+
+// Two game states are equal when their boards are equal. Score, history,
+// statistics, settings and colors are ignored, since positions are what search
+// and loop detection compare. Hash agrees with this, so states can be used
+// directly as HashMap keys.
+impl<const N: usize> PartialEq for GameState<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.grid == other.grid
+    }
+}
+
+impl<const N: usize> Eq for GameState<N> {}
+
+impl<const N: usize> Hash for GameState<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.grid.hash(state);
+    }
+}
+
+impl<const N: usize> GameState<N> {
+    /// Returns a 64-bit hash of the board
+    ///
+    /// Equal boards always hash equally, for any board size and any tile values.
+    /// Unlike packed(), distinct boards may collide, so use it for fast
+    /// duplicate detection rather than as an exact key. The value is stable
+    /// within one build but not guaranteed across Rust versions, so don't store it.
+    ///
+    /// # Returns
+    ///
+    /// * `u64` - The board's hash
+    pub fn board_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// === TESTS ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotated_boards_share_a_canonical_key() {
+        let board = GameState::from_grid([[2, 4, 8, 16], [0, 0, 0, 32], [0; 4], [0, 2, 0, 0]]);
+        let rotated = GameState::from_grid(rotate_grid_cw(&board.grid));
+        assert_ne!(board.packed(), rotated.packed());
+        assert_eq!(board.canonical(), rotated.canonical());
+
+        let mirrored = GameState::from_grid(flip_grid_horizontal(&board.grid));
+        assert_eq!(board.canonical(), mirrored.canonical());
+    }
+
+    #[test]
+    fn from_packed_round_trips_the_benchmark_boards() {
+        let game = GameState::from_packed(0x1000_0000_0000_0002);
+        assert_eq!(game.grid[0][0], 2);
+        assert_eq!(game.grid[3][3], 4);
+        assert_eq!(game.empty_cell_count(), 14);
+
+        let packed = 0x9876_4523_2310_1001;
+        assert_eq!(GameState::from_packed(packed).packed(), packed);
+        assert_eq!(GameState::from_grid(unpack_grid(packed)).packed(), packed);
+    }
+
+    #[test]
+    fn merge_preview_reports_the_single_merge_of_the_leading_pair() {
+        let game = GameState::from_grid([[2, 2, 2, 0], [0; 4], [0; 4], [0; 4]]);
+        assert_eq!(
+            game.merge_preview(Direction::Left),
+            vec![((0, 1), (0, 0), 4)]
+        );
+        assert_eq!(
+            game.merge_preview(Direction::Right),
+            vec![((0, 1), (0, 3), 4)]
+        );
+        assert!(game.merge_preview(Direction::Up).is_empty());
+        assert_eq!(game.grid[0], [2, 2, 2, 0]);
+    }
+
+    #[test]
+    fn legal_successor_accepts_one_turn_and_rejects_jumps() {
+        let before = GameState::from_grid([[2, 2, 0, 0], [0, 4, 0, 0], [0; 4], [0; 4]]);
+        let after = GameState::from_grid([[4, 0, 0, 0], [4, 0, 0, 0], [0; 4], [0, 0, 0, 2]]);
+        assert!(GameState::is_legal_successor(&before, &after));
+
+        let no_spawn = GameState::from_grid([[4, 0, 0, 0], [4, 0, 0, 0], [0; 4], [0; 4]]);
+        let jump = GameState::from_grid([[2048, 0, 0, 0], [0; 4], [0; 4], [0; 4]]);
+        assert!(!GameState::is_legal_successor(&before, &no_spawn));
+        assert!(!GameState::is_legal_successor(&before, &jump));
+        assert!(!GameState::is_legal_successor(&before, &before));
+    }
+
+    #[test]
+    fn rotating_clockwise_four_times_restores_the_board() {
+        let board = [[2, 4, 8, 16], [0, 0, 0, 32], [0; 4], [64, 0, 0, 128]];
+        let mut game = GameState::from_grid(board);
+        game.rotate_cw();
+        assert_eq!(
+            game.grid,
+            [[64, 0, 0, 2], [0, 0, 0, 4], [0, 0, 0, 8], [128, 0, 32, 16]]
+        );
+        game.rotate_ccw();
+        assert_eq!(game.grid, board);
+        for _ in 0..4 {
+            game.rotate_cw();
+        }
+        assert_eq!(game.grid, board);
+    }
+
+    #[test]
+    fn flips_mirror_the_board_and_undo_themselves() {
+        let board = [[2, 4, 8, 16], [0, 0, 0, 32], [0; 4], [64, 0, 0, 128]];
+        let mut game = GameState::from_grid(board);
+        game.flip_horizontal();
+        assert_eq!(
+            game.grid,
+            [[16, 8, 4, 2], [32, 0, 0, 0], [0; 4], [128, 0, 0, 64]]
+        );
+        game.flip_horizontal();
+        assert_eq!(game.grid, board);
+        game.flip_vertical();
+        assert_eq!(game.grid[0], [64, 0, 0, 128]);
+        game.flip_vertical();
+        assert_eq!(game.grid, board);
+    }
+
+    #[test]
+    fn equal_boards_hash_alike_whatever_their_palette() {
+        let grid = [[2, 4, 0, 0], [0, 8, 0, 0], [0; 4], [0; 4]];
+        let plain = GameState::from_grid(grid);
+        let mut dark = GameState::from_grid(grid);
+        dark.set_palette(crate::Palette::Dark);
+        assert!(plain == dark);
+        assert_eq!(plain.board_hash(), dark.board_hash());
+
+        let other = GameState::from_grid([[4, 2, 0, 0], [0, 8, 0, 0], [0; 4], [0; 4]]);
+        assert!(plain != other);
+        let seen: std::collections::HashSet<_> = [plain].into_iter().collect();
+        assert!(seen.contains(&dark));
+    }
+
+    /// Straightforward reference slide of one line toward index 0: drop the
+    /// gaps, then merge equal neighbors pairwise from the leading edge
+    fn reference_slide(line: [u32; 4]) -> [u32; 4] {
+        let tiles: Vec<u32> = line.into_iter().filter(|&value| value != 0).collect();
+        let mut merged = Vec::new();
+        let mut i = 0;
+        while i < tiles.len() {
+            if i + 1 < tiles.len() && tiles[i] == tiles[i + 1] {
+                merged.push(tiles[i] * 2);
+                i += 2;
+            } else {
+                merged.push(tiles[i]);
+                i += 1;
+            }
+        }
+        merged.resize(4, 0);
+        [merged[0], merged[1], merged[2], merged[3]]
+    }
+
+    #[test]
+    fn every_four_tile_line_matches_the_reference_in_all_directions() {
+        let values = [0, 2, 4, 8];
+        for index in 0..4usize.pow(4) {
+            let line: [u32; 4] = std::array::from_fn(|k| values[index / 4usize.pow(k as u32) % 4]);
+            let reversed = |mut line: [u32; 4]| {
+                line.reverse();
+                line
+            };
+            let toward_start = reference_slide(line);
+            let toward_end = reversed(reference_slide(reversed(line)));
+
+            let as_row = [line, [0; 4], [0; 4], [0; 4]];
+            let mut as_column = [[0; 4]; 4];
+            for (k, &value) in line.iter().enumerate() {
+                as_column[k][0] = value;
+            }
+
+            let mut game = GameState::from_grid(as_row);
+            game.move_left();
+            assert_eq!(game.grid[0], toward_start, "left {:?}", line);
+            let mut game = GameState::from_grid(as_row);
+            game.move_right();
+            assert_eq!(game.grid[0], toward_end, "right {:?}", line);
+            let mut game = GameState::from_grid(as_column);
+            game.move_up();
+            assert_eq!(game.column(0), Some(toward_start), "up {:?}", line);
+            let mut game = GameState::from_grid(as_column);
+            game.move_down();
+            assert_eq!(game.column(0), Some(toward_end), "down {:?}", line);
+        }
+    }
+
+    #[test]
+    fn move_preview_shows_where_every_tile_lands() {
+        let game = GameState::from_grid([[2, 2, 4, 0], [0, 0, 0, 8], [0; 4], [0; 4]]);
+        assert_eq!(
+            game.move_preview(Direction::Left),
+            vec![
+                ((0, 0), (0, 0), 4),
+                ((0, 1), (0, 0), 4),
+                ((0, 2), (0, 1), 4),
+                ((1, 3), (1, 0), 8),
+            ]
+        );
+        assert_eq!(
+            game.move_preview(Direction::Right),
+            vec![
+                ((0, 2), (0, 3), 4),
+                ((0, 1), (0, 2), 4),
+                ((0, 0), (0, 2), 4),
+                ((1, 3), (1, 3), 8),
+            ]
+        );
+        assert_eq!(game.grid[0], [2, 2, 4, 0]);
+    }
+
+    #[test]
+    fn a_full_line_of_equal_tiles_merges_into_two_pairs() {
+        for pass in [MergePass::Strict2048, MergePass::SinglePass] {
+            let rules = MergeRules {
+                multiplier: 2,
+                pass,
+            };
+            for value in [2, 4] {
+                let pair = value * 2;
+                for direction in Direction::ALL {
+                    let mut grid = [[value; 4]; 4];
+                    let slide = slide(&mut grid, direction, rules);
+                    assert_eq!(slide.merges, 8, "{:?} {:?} {}", pass, direction, value);
+                    assert_eq!(slide.gained, 8 * pair);
+
+                    let expected = match direction {
+                        Direction::Right => [[0, 0, pair, pair]; 4],
+                        Direction::Left => [[pair, pair, 0, 0]; 4],
+                        Direction::Down => [[0; 4], [0; 4], [pair; 4], [pair; 4]],
+                        Direction::Up => [[pair; 4], [pair; 4], [0; 4], [0; 4]],
+                    };
+                    assert_eq!(grid, expected, "{:?} {:?} {}", pass, direction, value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn slide_with_moves_matches_the_plain_slide() {
+        let values = [0, 2, 2, 4, 8];
+        for pass in [MergePass::Strict2048, MergePass::SinglePass] {
+            let rules = MergeRules {
+                multiplier: 2,
+                pass,
+            };
+            for index in 0..400u32 {
+                let grid: Grid<4> = std::array::from_fn(|i| {
+                    std::array::from_fn(|j| {
+                        values[((index * 7 + 13) * (i * 4 + j + 3) as u32 % 97 % 5) as usize]
+                    })
+                });
+                for direction in Direction::ALL {
+                    let mut plain = grid;
+                    let mut detailed = grid;
+                    let expected = slide(&mut plain, direction, rules);
+                    let (actual, moves) = slide_with_moves(&mut detailed, direction, rules);
+                    assert_eq!(detailed, plain, "{:?} {:?} {:?}", pass, direction, grid);
+                    assert_eq!(actual, expected, "{:?} {:?} {:?}", pass, direction, grid);
+                    assert_eq!(
+                        moves.len(),
+                        grid.iter().flatten().filter(|&&cell| cell != 0).count()
+                    );
+                }
+            }
+        }
+    }
+}
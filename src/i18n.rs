@@ -0,0 +1,214 @@
+// 2048 Game Localization - Translated User Interface Strings
+//
+// This module holds every piece of text draw() puts on screen, so the interface
+// can be switched between languages at runtime:
+// - Language, the languages with a full translation
+// - UiStrings, one language's string table
+//
+// Tile numbers and palette names are not translated.
+
+use crate::GameState;
+
+// === LANGUAGES ===
+
+/// Languages the interface is translated into, selected with set_language()
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// English, the default
+    #[default]
+    English,
+    /// French
+    French,
+    /// Spanish
+    Spanish,
+    /// German
+    German,
+}
+
+impl Language {
+    /// Every supported language
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::French,
+        Language::Spanish,
+        Language::German,
+    ];
+
+    /// Returns the string table for this language
+    pub fn strings(self) -> &'static UiStrings {
+        match self {
+            Language::English => &ENGLISH,
+            Language::French => &FRENCH,
+            Language::Spanish => &SPANISH,
+            Language::German => &GERMAN,
+        }
+    }
+}
+
+// === STRING TABLES ===
+
+/// Every piece of interface text in one language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiStrings {
+    /// Headline of the game over overlay
+    pub game_over: &'static str,
+    /// Instruction below the game over headline
+    pub restart_hint: &'static str,
+    /// Instruction shown on the game over overlay while a revive is available
+    pub revive_hint: &'static str,
+    /// Start of the banner shown when the target tile is reached
+    pub you_win: &'static str,
+    /// Label before the target value in the win banner, as in "Reached 1024!"
+    pub reached_target: &'static str,
+    /// Message covering the board while paused
+    pub paused: &'static str,
+    /// Prompt shown after the first press of R
+    pub confirm_restart: &'static str,
+    /// Label of the move number in the history viewer
+    pub viewing_move: &'static str,
+    /// Label introducing the palette name after pressing T
+    pub palette: &'static str,
+    /// Label of the score in the game over summary
+    pub score: &'static str,
+    /// Label of the move count in the game over summary
+    pub moves: &'static str,
+    /// Label of the highest tile in the game over summary
+    pub best_tile: &'static str,
+    /// Label of the game length in the game over summary
+    pub time: &'static str,
+    /// Message shown instead of the board in a window that is too small
+    pub window_too_small: &'static str,
+}
+
+const ENGLISH: UiStrings = UiStrings {
+    game_over: "Game Over!",
+    restart_hint: "Press Enter to restart",
+    revive_hint: "Press V to revive",
+    you_win: "You Win!",
+    reached_target: "Reached",
+    paused: "Paused - press P to resume",
+    confirm_restart: "Press R again to confirm restart",
+    viewing_move: "Viewing move",
+    palette: "Palette",
+    score: "Score",
+    moves: "Moves",
+    best_tile: "Best tile",
+    time: "Time",
+    window_too_small: "Window too small\nPlease enlarge it",
+};
+
+const FRENCH: UiStrings = UiStrings {
+    game_over: "Partie terminée !",
+    restart_hint: "Appuyez sur Entrée pour rejouer",
+    revive_hint: "Appuyez sur V pour continuer",
+    you_win: "Gagné !",
+    reached_target: "Atteint",
+    paused: "Pause - appuyez sur P pour reprendre",
+    confirm_restart: "Appuyez encore sur R pour recommencer",
+    viewing_move: "Coup",
+    palette: "Palette",
+    score: "Score",
+    moves: "Coups",
+    best_tile: "Meilleure tuile",
+    time: "Temps",
+    window_too_small: "Fenêtre trop petite\nVeuillez l'agrandir",
+};
+
+const SPANISH: UiStrings = UiStrings {
+    game_over: "¡Fin del juego!",
+    restart_hint: "Pulsa Intro para reiniciar",
+    revive_hint: "Pulsa V para continuar",
+    you_win: "¡Has ganado!",
+    reached_target: "Alcanzado",
+    paused: "En pausa - pulsa P para continuar",
+    confirm_restart: "Pulsa R otra vez para reiniciar",
+    viewing_move: "Movimiento",
+    palette: "Paleta",
+    score: "Puntos",
+    moves: "Movimientos",
+    best_tile: "Mejor ficha",
+    time: "Tiempo",
+    window_too_small: "Ventana demasiado pequeña\nAgrándala, por favor",
+};
+
+const GERMAN: UiStrings = UiStrings {
+    game_over: "Spiel vorbei!",
+    restart_hint: "Enter drücken für ein neues Spiel",
+    revive_hint: "V drücken zum Weiterspielen",
+    you_win: "Gewonnen!",
+    reached_target: "Erreicht",
+    paused: "Pause - P drücken zum Fortsetzen",
+    confirm_restart: "Zum Neustart erneut R drücken",
+    viewing_move: "Zug",
+    palette: "Palette",
+    score: "Punkte",
+    moves: "Züge",
+    best_tile: "Beste Kachel",
+    time: "Zeit",
+    window_too_small: "Fenster zu klein\nBitte vergrößern",
+};
+
+// === LANGUAGE SELECTION ===
+
+impl<const N: usize> GameState<N> {
+    /// Switches the interface text to another language
+    ///
+    /// Also replaces the game over overlay text with the language's defaults,
+    /// so call set_game_over_config() afterwards to customize it further.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language to display
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+        self.set_game_over_config(crate::GameOverConfig::for_language(language));
+    }
+
+    /// Returns the language the interface is displayed in
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns the interface text in the current language
+    pub fn strings(&self) -> &'static UiStrings {
+        self.language.strings()
+    }
+
+    /// Returns the banner celebrating the target tile in the current language,
+    /// such as "You Win! Reached 1024!"
+    pub fn win_banner(&self) -> String {
+        let strings = self.strings();
+        format!(
+            "{} {} {}!",
+            strings.you_win,
+            strings.reached_target,
+            self.target()
+        )
+    }
+}
+
+// === TESTS ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switching_language_translates_the_game_over_title() {
+        let mut game = GameState::<4>::new();
+        assert_eq!(game.strings().game_over, "Game Over!");
+        game.set_language(Language::French);
+        assert_eq!(game.language(), Language::French);
+        assert_eq!(game.strings().game_over, "Partie terminée !");
+        assert_eq!(game.game_over_config().title, "Partie terminée !");
+    }
+
+    #[test]
+    fn win_banner_names_the_target_in_the_current_language() {
+        let mut game = GameState::<4>::new();
+        game.set_target(1024).unwrap();
+        assert_eq!(game.win_banner(), "You Win! Reached 1024!");
+        game.set_language(Language::German);
+        assert_eq!(game.win_banner(), "Gewonnen! Erreicht 1024!");
+    }
+}
@@ -0,0 +1,188 @@
+// 2048 Game Leaderboard - Local High Score Table
+//
+// This module keeps a table of the best finished games in a JSON file:
+// - LeaderboardEntry, a serializable record of one game built from GameState
+// - Insertion that keeps the table sorted by score and capped to a top N
+// - Loading and saving the table, where a missing or unreadable file simply
+//   starts a fresh table instead of failing
+
+use crate::GameState;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of entries kept by record_to_leaderboard()
+pub const LEADERBOARD_SIZE: usize = 10;
+
+// === LEADERBOARD ENTRY ===
+
+/// One finished game as recorded on the leaderboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LeaderboardEntry {
+    /// Points scored
+    pub score: u32,
+    /// Number of successful moves played
+    pub moves: u32,
+    /// Length of the game in whole seconds
+    pub duration_secs: u64,
+    /// Highest tile on the board
+    pub max_tile: u32,
+    /// Seed the game was started from, so it can be replayed, if it had one
+    pub seed: Option<u64>,
+    /// When the entry was created, in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl<const N: usize> GameState<N> {
+    /// Builds a leaderboard entry for the current game, stamped with the current time
+    ///
+    /// # Returns
+    ///
+    /// * `LeaderboardEntry` - The game's stats() plus its seed and a timestamp
+    pub fn leaderboard_entry(&self) -> LeaderboardEntry {
+        let stats = self.stats();
+        LeaderboardEntry {
+            score: stats.score,
+            moves: stats.moves,
+            duration_secs: stats.duration_secs,
+            max_tile: stats.max_tile,
+            seed: self.seed(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or(0),
+        }
+    }
+}
+
+// === TABLE MAINTENANCE ===
+
+/// Inserts an entry into a table sorted by descending score, keeping at most `limit`
+///
+/// An entry that ties an existing score is placed after it, so earlier results
+/// keep their rank.
+///
+/// # Arguments
+///
+/// * `entries` - The table, already sorted by descending score
+/// * `entry` - The entry to insert
+/// * `limit` - Maximum number of entries to keep
+///
+/// # Returns
+///
+/// * `Option<usize>` - The entry's 0-based rank, or None if it didn't make the cut
+pub fn insert_entry(
+    entries: &mut Vec<LeaderboardEntry>,
+    entry: LeaderboardEntry,
+    limit: usize,
+) -> Option<usize> {
+    let rank = entries
+        .iter()
+        .position(|existing| existing.score < entry.score)
+        .unwrap_or(entries.len());
+    entries.insert(rank, entry);
+    entries.truncate(limit);
+    (rank < limit).then_some(rank)
+}
+
+// === FILE STORAGE ===
+
+/// Reads a leaderboard file
+///
+/// A missing file is an empty leaderboard. A file that can't be read or parsed
+/// is logged and also treated as empty, so the next save replaces it with a
+/// fresh table rather than leaving the player stuck.
+///
+/// # Arguments
+///
+/// * `path` - Path of the JSON leaderboard file
+///
+/// # Returns
+///
+/// * `Vec<LeaderboardEntry>` - The entries sorted by descending score
+pub fn load_leaderboard(path: &Path) -> Vec<LeaderboardEntry> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(error) => {
+            if error.kind() != std::io::ErrorKind::NotFound {
+                log::warn!("failed to read leaderboard {}: {}", path.display(), error);
+            }
+            return Vec::new();
+        }
+    };
+
+    match serde_json::from_str::<Vec<LeaderboardEntry>>(&contents) {
+        Ok(mut entries) => {
+            // Don't trust the file's order; it may have been edited by hand
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.score));
+            entries
+        }
+        Err(error) => {
+            log::warn!("ignoring corrupt leaderboard {}: {}", path.display(), error);
+            Vec::new()
+        }
+    }
+}
+
+/// Records a finished game in a leaderboard file
+///
+/// Loads the table with load_leaderboard(), inserts the entry keeping the top
+/// LEADERBOARD_SIZE scores, and writes the table back as JSON.
+///
+/// # Arguments
+///
+/// * `path` - Path of the JSON leaderboard file
+/// * `entry` - The game to record
+///
+/// # Returns
+///
+/// * `Result<Option<usize>, String>` - The entry's 0-based rank (None if it
+///   didn't make the table), or Err if the file couldn't be written
+pub fn record_to_leaderboard(
+    path: &Path,
+    entry: LeaderboardEntry,
+) -> Result<Option<usize>, String> {
+    let mut entries = load_leaderboard(path);
+    let rank = insert_entry(&mut entries, entry, LEADERBOARD_SIZE);
+
+    let json = serde_json::to_string_pretty(&entries)
+        .map_err(|error| format!("failed to encode leaderboard: {}", error))?;
+    fs::write(path, json)
+        .map_err(|error| format!("failed to write leaderboard {}: {}", path.display(), error))?;
+    Ok(rank)
+}
+
+// === TESTS ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: u32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            score,
+            moves: 1,
+            duration_secs: 0,
+            max_tile: 2,
+            seed: None,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn inserted_entries_stay_sorted_and_capped() {
+        let mut table = Vec::new();
+        assert_eq!(insert_entry(&mut table, entry(10), 3), Some(0));
+        assert_eq!(insert_entry(&mut table, entry(30), 3), Some(0));
+        assert_eq!(insert_entry(&mut table, entry(20), 3), Some(1));
+        assert_eq!(insert_entry(&mut table, entry(5), 3), None);
+        assert_eq!(insert_entry(&mut table, entry(25), 3), Some(1));
+        let scores: Vec<u32> = table.iter().map(|entry| entry.score).collect();
+        assert_eq!(scores, vec![30, 25, 20]);
+
+        // Ties keep the earlier result ahead
+        assert_eq!(insert_entry(&mut table, entry(25), 3), Some(2));
+        assert!(load_leaderboard(Path::new("/nonexistent/leaderboard.json")).is_empty());
+    }
+}
@@ -5,7 +5,7 @@
 // tiles to reach the 2048 tile.
 
 use ggez::{conf, event, ContextBuilder, GameResult};
-use rust_2048_game::GameState;
+use rust_2048_game::{GameState, SandboxGame};
 
 /// Main function that initializes and runs the 2048 game
 ///
@@ -26,15 +26,29 @@ fn main() -> GameResult {
         .window_setup(conf::WindowSetup::default().title("2048"))
         // Set window dimensions based on grid size and cell size
         // This ensures the window is perfectly sized for our 4x4 grid
-        .window_mode(conf::WindowMode::default().dimensions(
-            rust_2048_game::GRID_SIZE as f32 * rust_2048_game::CELL_SIZE,
-            rust_2048_game::GRID_SIZE as f32 * rust_2048_game::CELL_SIZE,
-        ));
+        .window_mode(
+            conf::WindowMode::default()
+                .dimensions(
+                    rust_2048_game::GRID_SIZE as f32 * rust_2048_game::CELL_SIZE,
+                    rust_2048_game::GRID_SIZE as f32 * rust_2048_game::CELL_SIZE,
+                )
+                // Don't let the window shrink below the size where tiles stay legible
+                .min_dimensions(
+                    rust_2048_game::GRID_SIZE as f32 * rust_2048_game::MIN_CELL_SIZE,
+                    rust_2048_game::GRID_SIZE as f32 * rust_2048_game::MIN_CELL_SIZE,
+                ),
+        );
 
     // Build the graphics context and event loop from the configuration
     // The context handles rendering and the event loop manages input/update cycles
     let (ctx, event_loop) = cb.build()?;
 
+    // With --sandbox, start a game whose board size can be changed with , and .
+    if std::env::args().any(|arg| arg == "--sandbox") {
+        let state = SandboxGame::new(rust_2048_game::GRID_SIZE as usize);
+        return event::run(ctx, event_loop, state);
+    }
+
     // Initialize the game state with an empty grid and add two starting tiles
     // GameState::new() sets up the initial game board with two random tiles (2 or 4)
     let state = GameState::new();
@@ -0,0 +1,137 @@
+// 2048 Game Row Practice - Learning to Slide and Merge on a Single Row
+//
+// A tutorial mode for beginners: every row but one is blocked, so the player
+// learns how tiles slide and pair up in isolation before moving on to the
+// full board:
+// - Blocked cells, which stay empty and never receive a spawned tile
+// - Vertical moves are refused, since they could only push tiles into blocked
+//   rows; left and right play exactly as on the full board
+// - finish_row_practice() unblocks the board without losing the tiles
+//
+// Starting a practice begins a new game; restarting keeps practicing the same
+// row until the practice is finished.
+
+use crate::{Direction, GameState};
+
+// === BLOCKED CELLS ===
+
+impl<const N: usize> GameState<N> {
+    /// Returns whether a cell is blocked and can never hold a tile
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index of the cell
+    /// * `col` - Column index of the cell
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True for a board cell outside the active row during row
+    ///   practice; false otherwise, including for cells off the board
+    pub fn is_cell_blocked(&self, row: usize, col: usize) -> bool {
+        row < N && col < N && self.practice_row.is_some_and(|active| active != row)
+    }
+
+    /// Returns whether the blocked cells rule out moving in a direction
+    pub(crate) fn blocks_direction(&self, direction: Direction) -> bool {
+        self.practice_row.is_some() && matches!(direction, Direction::Up | Direction::Down)
+    }
+}
+
+// === ROW PRACTICE ===
+
+impl<const N: usize> GameState<N> {
+    /// Starts a new game in which only one row can be played
+    ///
+    /// The other rows are blocked: the starting tiles and every later spawn
+    /// land in the active row, and only left and right moves are accepted. The
+    /// game ends once the row is full and no two neighbors match.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Index of the row left open
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), String>` - Err if `row` is not a row of the board
+    pub fn start_row_practice(&mut self, row: usize) -> Result<(), String> {
+        if row >= N {
+            return Err(format!("row {} is outside a board of {} rows", row, N));
+        }
+        self.practice_row = Some(row);
+        self.reset_board();
+        Ok(())
+    }
+
+    /// Ends row practice and opens up the whole board
+    ///
+    /// The tiles of the practice row stay where they are, so the game simply
+    /// carries on with all four directions and every cell available.
+    pub fn finish_row_practice(&mut self) {
+        self.practice_row = None;
+        self.refresh_game_over();
+    }
+
+    /// Returns the row being practiced, or None when the whole board is open
+    pub fn practice_row(&self) -> Option<usize> {
+        self.practice_row
+    }
+}
+
+// === TESTS ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn spawns_only_land_in_the_active_row() {
+        for seed in 0..50 {
+            let mut game = GameState::<4>::blank();
+            game.rng = StdRng::seed_from_u64(seed);
+            game.start_row_practice(1).unwrap();
+            game.play_random(200, &mut StdRng::seed_from_u64(seed));
+
+            for (i, row) in game.grid.iter().enumerate() {
+                if i != 1 {
+                    assert_eq!(*row, [0; 4], "seed {}", seed);
+                }
+            }
+            assert!(game.game_over, "seed {}", seed);
+            assert!(!game.apply_move(Direction::Up));
+
+            game.finish_row_practice();
+            assert!(!game.is_cell_blocked(0, 0));
+            assert!(!game.game_over);
+        }
+    }
+
+    #[test]
+    fn risky_moves_count_blocked_cells_as_occupied() {
+        let mut game = GameState::<4>::new();
+        game.start_row_practice(0).unwrap();
+        let row = |first: [u32; 4]| -> Grid<4> { [first, [0; 4], [0; 4], [0; 4]] };
+
+        // Left leaves a single open cell, and no spawn there can merge
+        game.grid = row([0, 4, 8, 16]);
+        assert!(game.move_leads_to_game_over(Direction::Left));
+        assert!(!game.move_leads_to_game_over(Direction::Up));
+
+        game.grid = row([4, 4, 0, 0]);
+        assert!(!game.move_leads_to_game_over(Direction::Left));
+    }
+
+    #[test]
+    fn worst_spawn_never_fills_a_blocked_cell() {
+        let mut game = GameState::<4>::new();
+        game.start_row_practice(2).unwrap();
+        game.grid = [[0; 4], [0; 4], [2, 4, 8, 0], [0; 4]];
+        game.spawned_cells.clear();
+        game.worst_spawn();
+        assert_eq!(game.spawned_cells(), &[(2, 3)]);
+
+        game.worst_spawn();
+        assert_eq!(game.spawned_cells().len(), 1);
+    }
+}
@@ -0,0 +1,216 @@
+// 2048 Game Replays - Reproducing and Watching Recorded Games
+//
+// Every spawn comes from the game's own seeded generator, so a game is fully
+// described by its seed and the moves played. This module turns such a record
+// back into a game:
+// - replay_from(), which replays a record instantly
+// - A timed playback mode that update() steps through one move per interval,
+//   with adjustable speed and pausing, for watching a game as a spectator
+
+use crate::{Direction, GameState};
+use std::time::Duration;
+
+/// Time between moves during playback unless changed with set_playback_interval()
+pub const DEFAULT_PLAYBACK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Shortest allowed time between moves during playback
+pub const MIN_PLAYBACK_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Longest allowed time between moves during playback
+pub const MAX_PLAYBACK_INTERVAL: Duration = Duration::from_secs(4);
+
+// === PLAYBACK STATE ===
+
+/// Progress through a recorded game being played back
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Playback {
+    /// The recorded moves, in the order they were played
+    moves: Vec<Direction>,
+    /// Index of the next move to apply
+    next: usize,
+    /// Time accumulated toward the next move
+    elapsed: Duration,
+    /// Whether playback is held on the current move
+    paused: bool,
+}
+
+// === INSTANT REPLAY ===
+
+impl<const N: usize> GameState<N> {
+    /// Rebuilds a game from its seed and the moves that were played
+    ///
+    /// Starts from the same opening tiles as reset_with_seed(), then applies
+    /// every move with apply_move(), so the spawns match the original game.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed the recorded game was started from
+    /// * `moves` - The recorded moves, in order
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The game as it stood after the last recorded move
+    pub fn replay_from(seed: u64, moves: &[Direction]) -> Self {
+        let mut state = Self::blank();
+        state.reset_with_seed(seed);
+        for &direction in moves {
+            state.apply_move(direction);
+        }
+        state
+    }
+}
+
+// === TIMED PLAYBACK ===
+
+impl<const N: usize> GameState<N> {
+    /// Restarts the game from `seed` and plays `moves` back over time
+    ///
+    /// Nothing is applied immediately: each call to advance_playback(), made by
+    /// update() every frame, applies one recorded move per elapsed
+    /// playback_interval(). Player moves are ignored until playback finishes or
+    /// is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed the recorded game was started from
+    /// * `moves` - The recorded moves, in order
+    pub fn start_playback(&mut self, seed: u64, moves: Vec<Direction>) {
+        self.reset_with_seed(seed);
+        self.playback = Some(Playback {
+            moves,
+            ..Playback::default()
+        });
+    }
+
+    /// Advances playback by some elapsed time, applying every move that is due
+    ///
+    /// Leftover time carries over to the next call, so playback keeps an exact
+    /// pace of one move per interval regardless of frame rate. Playback ends on
+    /// its own after the last recorded move.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - Time elapsed since the previous call
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - Number of recorded moves applied by this call
+    pub fn advance_playback(&mut self, delta: Duration) -> usize {
+        let interval = self.playback_interval;
+        let Some(playback) = self.playback.as_mut() else {
+            return 0;
+        };
+        if playback.paused {
+            return 0;
+        }
+
+        playback.elapsed += delta;
+        let mut due = Vec::new();
+        while playback.elapsed >= interval && playback.next < playback.moves.len() {
+            playback.elapsed -= interval;
+            due.push(playback.moves[playback.next]);
+            playback.next += 1;
+        }
+        if playback.next == playback.moves.len() {
+            self.playback = None;
+        }
+
+        for &direction in &due {
+            self.apply_move(direction);
+        }
+        due.len()
+    }
+
+    /// Returns whether a recorded game is being played back
+    pub fn is_playing_back(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// Stops playback, leaving the board at the last move played
+    pub fn stop_playback(&mut self) {
+        self.playback = None;
+    }
+
+    /// Holds or resumes playback on the current move
+    pub fn toggle_playback_pause(&mut self) {
+        if let Some(playback) = self.playback.as_mut() {
+            playback.paused = !playback.paused;
+        }
+    }
+
+    /// Returns whether playback is active but held on the current move
+    pub fn is_playback_paused(&self) -> bool {
+        self.playback
+            .as_ref()
+            .is_some_and(|playback| playback.paused)
+    }
+
+    /// Sets the time between moves during playback
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Time between moves, clamped to MIN_PLAYBACK_INTERVAL..=
+    ///   MAX_PLAYBACK_INTERVAL
+    pub fn set_playback_interval(&mut self, interval: Duration) {
+        self.playback_interval = interval.clamp(MIN_PLAYBACK_INTERVAL, MAX_PLAYBACK_INTERVAL);
+    }
+
+    /// Returns the time between moves during playback
+    pub fn playback_interval(&self) -> Duration {
+        self.playback_interval
+    }
+
+    /// Doubles the playback speed, down to MIN_PLAYBACK_INTERVAL between moves
+    pub fn speed_up_playback(&mut self) {
+        self.set_playback_interval(self.playback_interval / 2);
+    }
+
+    /// Halves the playback speed, up to MAX_PLAYBACK_INTERVAL between moves
+    pub fn slow_down_playback(&mut self) {
+        self.set_playback_interval(self.playback_interval * 2);
+    }
+}
+
+// === TESTS ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn playback_advances_one_move_per_interval() {
+        let moves = vec![
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ];
+        let mut game = GameState::<4>::blank();
+        game.set_playback_interval(Duration::from_millis(100));
+        game.start_playback(7, moves.clone());
+
+        assert_eq!(game.advance_playback(Duration::from_millis(99)), 0);
+        assert_eq!(game.advance_playback(Duration::from_millis(1)), 1);
+        assert_eq!(game.grid, GameState::<4>::replay_from(7, &moves[..1]).grid);
+
+        game.toggle_playback_pause();
+        assert_eq!(game.advance_playback(Duration::from_millis(500)), 0);
+        game.toggle_playback_pause();
+
+        // Leftover time carries over to the next move
+        assert_eq!(game.advance_playback(Duration::from_millis(250)), 2);
+        assert_eq!(game.advance_playback(Duration::from_millis(50)), 1);
+        assert_eq!(game.grid, GameState::<4>::replay_from(7, &moves[..4]).grid);
+        assert_eq!(
+            game.score,
+            GameState::<4>::replay_from(7, &moves[..4]).score
+        );
+        assert!(game.is_playing_back());
+
+        assert_eq!(game.advance_playback(Duration::from_millis(100)), 1);
+        assert!(!game.is_playing_back());
+    }
+}
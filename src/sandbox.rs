@@ -0,0 +1,204 @@
+// 2048 Game Sandbox - Switching the Board Size at Runtime
+//
+// The board size of a GameState is part of its type, so a game cannot change
+// size in place. The sandbox holds one game of any supported size and rebuilds
+// it at a new size on request:
+// - SandboxGame, a game of 2x2 up to 8x8 whose size can change while running
+// - Keyboard controls (comma and period) that shrink or grow the board, on top
+//   of every regular control
+//
+// The window keeps its size; draw() scales the board to fit it.
+
+use crate::GameState;
+#[cfg(feature = "gui")]
+use ggez::{
+    event::EventHandler,
+    input::keyboard::{KeyCode, KeyInput},
+    Context, GameResult,
+};
+
+/// Smallest board size the sandbox can switch to
+pub const MIN_SANDBOX_SIZE: usize = 2;
+
+/// Largest board size the sandbox can switch to, the most that still fits the
+/// window at MIN_CELL_SIZE
+pub const MAX_SANDBOX_SIZE: usize = 8;
+
+// === SANDBOX GAME ===
+
+/// A game whose board size can be changed while the program runs
+///
+/// Each variant holds a regular GameState of that size; the number is the side
+/// length of the board.
+pub enum SandboxGame {
+    /// A 2x2 board
+    Size2(GameState<2>),
+    /// A 3x3 board
+    Size3(GameState<3>),
+    /// The classic 4x4 board
+    Size4(GameState<4>),
+    /// A 5x5 board
+    Size5(GameState<5>),
+    /// A 6x6 board
+    Size6(GameState<6>),
+    /// A 7x7 board
+    Size7(GameState<7>),
+    /// An 8x8 board
+    Size8(GameState<8>),
+}
+
+/// Runs the same expression on the game inside any SandboxGame variant
+macro_rules! with_game {
+    ($sandbox:expr, $game:ident => $body:expr) => {
+        match $sandbox {
+            SandboxGame::Size2($game) => $body,
+            SandboxGame::Size3($game) => $body,
+            SandboxGame::Size4($game) => $body,
+            SandboxGame::Size5($game) => $body,
+            SandboxGame::Size6($game) => $body,
+            SandboxGame::Size7($game) => $body,
+            SandboxGame::Size8($game) => $body,
+        }
+    };
+}
+
+impl SandboxGame {
+    /// Starts a fresh game on a board of the given size
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Side length of the board, clamped to
+    ///   MIN_SANDBOX_SIZE..=MAX_SANDBOX_SIZE
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new game with the default starting tiles
+    pub fn new(size: usize) -> Self {
+        match size.clamp(MIN_SANDBOX_SIZE, MAX_SANDBOX_SIZE) {
+            2 => SandboxGame::Size2(GameState::default()),
+            3 => SandboxGame::Size3(GameState::default()),
+            4 => SandboxGame::Size4(GameState::default()),
+            5 => SandboxGame::Size5(GameState::default()),
+            6 => SandboxGame::Size6(GameState::default()),
+            7 => SandboxGame::Size7(GameState::default()),
+            _ => SandboxGame::Size8(GameState::default()),
+        }
+    }
+
+    /// Returns the side length of the current board
+    pub fn size(&self) -> usize {
+        match self {
+            SandboxGame::Size2(_) => 2,
+            SandboxGame::Size3(_) => 3,
+            SandboxGame::Size4(_) => 4,
+            SandboxGame::Size5(_) => 5,
+            SandboxGame::Size6(_) => 6,
+            SandboxGame::Size7(_) => 7,
+            SandboxGame::Size8(_) => 8,
+        }
+    }
+
+    /// Replaces the game with a fresh one on a board of another size
+    ///
+    /// The current game is discarded; progress does not carry over.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Side length of the new board, clamped to
+    ///   MIN_SANDBOX_SIZE..=MAX_SANDBOX_SIZE
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The size actually used after clamping
+    pub fn set_size(&mut self, size: usize) -> usize {
+        *self = SandboxGame::new(size);
+        self.size()
+    }
+
+    /// Starts a fresh game on a board one size larger, if allowed
+    pub fn grow(&mut self) -> usize {
+        self.set_size(self.size() + 1)
+    }
+
+    /// Starts a fresh game on a board one size smaller, if allowed
+    pub fn shrink(&mut self) -> usize {
+        self.set_size(self.size().saturating_sub(1))
+    }
+
+    /// Returns the board as rows of tile values, whatever its size
+    pub fn rows(&self) -> Vec<Vec<u32>> {
+        with_game!(self, game => game.grid.iter().map(|row| row.to_vec()).collect())
+    }
+}
+
+// === EVENT HANDLER IMPLEMENTATION ===
+
+#[cfg(feature = "gui")]
+impl EventHandler<ggez::GameError> for SandboxGame {
+    fn update(&mut self, ctx: &mut Context) -> GameResult {
+        with_game!(self, game => game.update(ctx))
+    }
+
+    fn draw(&mut self, ctx: &mut Context) -> GameResult {
+        with_game!(self, game => game.draw(ctx))
+    }
+
+    /// Handles the size keys, then passes every other key to the game
+    ///
+    /// Comma shrinks the board and period grows it, each starting a fresh game.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The ggez context
+    /// * `key` - The key input event containing keycode information
+    /// * `repeat` - Whether this is a repeated key press
+    ///
+    /// # Returns
+    ///
+    /// * `GameResult` - Ok if handled successfully
+    fn key_down_event(&mut self, ctx: &mut Context, key: KeyInput, repeat: bool) -> GameResult {
+        match key.keycode {
+            Some(KeyCode::Comma) if !repeat => {
+                self.shrink();
+                Ok(())
+            }
+            Some(KeyCode::Period) if !repeat => {
+                self.grow();
+                Ok(())
+            }
+            _ => with_game!(self, game => game.key_down_event(ctx, key, repeat)),
+        }
+    }
+
+    fn key_up_event(&mut self, ctx: &mut Context, key: KeyInput) -> GameResult {
+        with_game!(self, game => game.key_up_event(ctx, key))
+    }
+
+    fn focus_event(&mut self, ctx: &mut Context, gained: bool) -> GameResult {
+        with_game!(self, game => game.focus_event(ctx, gained))
+    }
+}
+
+// === TESTS ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_size_clamps_and_starts_a_fresh_board() {
+        let mut sandbox = SandboxGame::new(4);
+        assert_eq!(sandbox.size(), 4);
+
+        assert_eq!(sandbox.set_size(20), MAX_SANDBOX_SIZE);
+        let rows = sandbox.rows();
+        assert_eq!(rows.len(), 8);
+        assert!(rows.iter().all(|row| row.len() == 8));
+        assert_eq!(rows.iter().flatten().filter(|&&cell| cell != 0).count(), 2);
+
+        assert_eq!(sandbox.set_size(0), MIN_SANDBOX_SIZE);
+        assert_eq!(sandbox.shrink(), 2);
+        assert_eq!(sandbox.grow(), 3);
+        assert_eq!(sandbox.rows().len(), 3);
+    }
+}